@@ -1,3 +1,4 @@
+pub mod outdated;
 pub mod transform;
 pub mod types;
 
@@ -19,6 +20,10 @@ pub struct ValidateAndBuildDeployResponse {
     pub has_failures: bool,
 }
 
+pub use outdated::{
+    find_outdated_widgets, AppWidgetVersion, DeployedVersionStatus, OutdatedWidgetsResponse,
+    WidgetVersionReport,
+};
 pub use transform::{app_path_extractor, transform_widgets_to_build_requests, widget_id_extractor};
 
 async fn process_widgets(
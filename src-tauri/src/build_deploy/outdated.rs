@@ -0,0 +1,345 @@
+use rayon::prelude::*;
+use semver::Version;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::types::AppInput;
+use crate::data_processing::mendix_filters::Widget;
+use crate::mendix::{collect_app_widget_inventory, get_installed_mendix_apps, AppWidgetInventory};
+use crate::storage::load_widgets_ordered;
+use crate::utils::find_highest_version_in_dist;
+use crate::widget_preview::metadata::find_widget_id_in_directory;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DeployedVersionStatus {
+    Older,
+    Same,
+    Newer,
+    Missing,
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppWidgetVersion {
+    pub app_name: String,
+    pub app_path: String,
+    pub deployed_version: Option<String>,
+    pub status: DeployedVersionStatus,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WidgetVersionReport {
+    pub widget_id: String,
+    pub caption: String,
+    pub path: String,
+    pub local_version: Option<String>,
+    pub apps: Vec<AppWidgetVersion>,
+    pub outdated_app_paths: Vec<String>,
+}
+
+/// Per-widget report plus the selections to pass to `validate_and_deploy_only`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OutdatedWidgetsResponse {
+    pub widgets: Vec<WidgetVersionReport>,
+    pub selected_widget_ids: Vec<String>,
+    pub selected_app_paths: Vec<String>,
+}
+
+/// How a registered widget is recognised inside a deployed package
+struct LocalWidget {
+    version: Option<Version>,
+    xml_widget_id: Option<String>,
+    mpk_file_names: Vec<String>,
+}
+
+fn list_mpk_file_names(dir: &Path) -> Vec<String> {
+    fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.file_name().to_string_lossy().to_string())
+                .filter(|name| name.to_lowercase().ends_with(".mpk"))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn describe_local_widget(widget: &Widget) -> LocalWidget {
+    let dist_version_dir: Option<PathBuf> = find_highest_version_in_dist(&widget.path);
+    let version = dist_version_dir
+        .as_ref()
+        .and_then(|dir| dir.file_name())
+        .and_then(|name| Version::parse(&name.to_string_lossy()).ok());
+    let mpk_file_names = dist_version_dir
+        .as_deref()
+        .map(list_mpk_file_names)
+        .unwrap_or_default();
+
+    LocalWidget {
+        version,
+        xml_widget_id: find_widget_id_in_directory(&Path::new(&widget.path).join("src")),
+        mpk_file_names,
+    }
+}
+
+/// Highest version among the app's packages that contain the widget
+fn find_deployed_version(local: &LocalWidget, inventory: &AppWidgetInventory) -> Option<String> {
+    inventory
+        .packages
+        .iter()
+        .filter(|package| {
+            let id_matches = local
+                .xml_widget_id
+                .as_ref()
+                .map(|id| package.widgets.iter().any(|widget| &widget.id == id))
+                .unwrap_or(false);
+            let file_matches = local
+                .mpk_file_names
+                .iter()
+                .any(|name| name.eq_ignore_ascii_case(&package.file_name));
+            id_matches || file_matches
+        })
+        .filter_map(|package| package.version.clone())
+        .max_by(|a, b| match (Version::parse(a), Version::parse(b)) {
+            (Ok(a), Ok(b)) => a.cmp(&b),
+            _ => a.cmp(b),
+        })
+}
+
+fn classify_deployed_version(
+    local_version: Option<&Version>,
+    deployed_version: Option<&str>,
+) -> DeployedVersionStatus {
+    let deployed_version = match deployed_version {
+        Some(version) => version,
+        None => return DeployedVersionStatus::Missing,
+    };
+
+    match (local_version, Version::parse(deployed_version)) {
+        (Some(local), Ok(deployed)) if deployed < *local => DeployedVersionStatus::Older,
+        (Some(local), Ok(deployed)) if deployed > *local => DeployedVersionStatus::Newer,
+        (Some(_), Ok(_)) => DeployedVersionStatus::Same,
+        _ => DeployedVersionStatus::Unknown,
+    }
+}
+
+fn create_widget_version_report(
+    widget: &Widget,
+    apps: &[AppInput],
+    inventories: &[AppWidgetInventory],
+) -> WidgetVersionReport {
+    let local = describe_local_widget(widget);
+
+    let app_versions: Vec<AppWidgetVersion> = apps
+        .iter()
+        .zip(inventories)
+        .map(|(app, inventory)| {
+            let deployed_version = find_deployed_version(&local, inventory);
+            AppWidgetVersion {
+                app_name: app.name.clone(),
+                app_path: app.path.clone(),
+                status: classify_deployed_version(
+                    local.version.as_ref(),
+                    deployed_version.as_deref(),
+                ),
+                deployed_version,
+            }
+        })
+        .collect();
+
+    let outdated_app_paths = app_versions
+        .iter()
+        .filter(|app| app.status == DeployedVersionStatus::Older)
+        .map(|app| app.app_path.clone())
+        .collect();
+
+    WidgetVersionReport {
+        widget_id: widget.id.clone(),
+        caption: widget.caption.clone(),
+        path: widget.path.clone(),
+        local_version: local.version.map(|version| version.to_string()),
+        apps: app_versions,
+        outdated_app_paths,
+    }
+}
+
+pub fn build_outdated_widgets_response(
+    widgets: &[Widget],
+    apps: &[AppInput],
+) -> OutdatedWidgetsResponse {
+    let inventories: Vec<AppWidgetInventory> = apps
+        .par_iter()
+        .map(|app| collect_app_widget_inventory(&app.path))
+        .collect();
+
+    let reports: Vec<WidgetVersionReport> = widgets
+        .iter()
+        .map(|widget| create_widget_version_report(widget, apps, &inventories))
+        .collect();
+
+    let selected_widget_ids = reports
+        .iter()
+        .filter(|report| !report.outdated_app_paths.is_empty())
+        .map(|report| report.widget_id.clone())
+        .collect();
+
+    let selected_app_paths = apps
+        .iter()
+        .filter(|app| {
+            reports
+                .iter()
+                .any(|report| report.outdated_app_paths.contains(&app.path))
+        })
+        .map(|app| app.path.clone())
+        .collect();
+
+    OutdatedWidgetsResponse {
+        widgets: reports,
+        selected_widget_ids,
+        selected_app_paths,
+    }
+}
+
+fn load_installed_apps_as_inputs() -> Result<Vec<AppInput>, String> {
+    Ok(get_installed_mendix_apps()?
+        .into_iter()
+        .map(|app| AppInput {
+            name: app.name,
+            path: app.path,
+        })
+        .collect())
+}
+
+/// Compare registered widgets with the copies deployed in each app
+///
+/// When `apps` is omitted every installed app is scanned.
+#[tauri::command]
+pub fn find_outdated_widgets(
+    apps: Option<Vec<AppInput>>,
+) -> Result<OutdatedWidgetsResponse, String> {
+    let widgets = load_widgets_ordered()?;
+    let apps = match apps {
+        Some(apps) => apps,
+        None => load_installed_apps_as_inputs()?,
+    };
+
+    Ok(build_outdated_widgets_response(&widgets, &apps))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+
+    const WIDGET_XML: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<widget id="com.example.calendar.Calendar" xmlns="http://www.mendix.com/widget/1.0/">
+    <name>Calendar</name>
+    <description>Shows events</description>
+    <properties/>
+</widget>"#;
+
+    fn package_xml(version: &str) -> String {
+        format!(
+            r#"<package><clientModule name="Calendar" version="{}"><widgetFiles><widgetFile path="Calendar.xml"/></widgetFiles></clientModule></package>"#,
+            version
+        )
+    }
+
+    fn write_deployed_mpk(app_dir: &Path, file_name: &str, version: &str) {
+        let widgets_dir = app_dir.join("widgets");
+        fs::create_dir_all(&widgets_dir).unwrap();
+        let mut writer = zip::ZipWriter::new(File::create(widgets_dir.join(file_name)).unwrap());
+        writer
+            .start_file("package.xml", SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(package_xml(version).as_bytes()).unwrap();
+        writer
+            .start_file("Calendar.xml", SimpleFileOptions::default())
+            .unwrap();
+        writer.write_all(WIDGET_XML.as_bytes()).unwrap();
+        writer.finish().unwrap();
+    }
+
+    fn app_input(dir: &Path, name: &str) -> AppInput {
+        AppInput {
+            name: name.to_string(),
+            path: dir.to_string_lossy().to_string(),
+        }
+    }
+
+    #[test]
+    fn test_classify_deployed_version() {
+        let local = Version::parse("1.2.0").unwrap();
+
+        assert_eq!(
+            classify_deployed_version(Some(&local), Some("1.1.9")),
+            DeployedVersionStatus::Older
+        );
+        assert_eq!(
+            classify_deployed_version(Some(&local), Some("1.2.0")),
+            DeployedVersionStatus::Same
+        );
+        assert_eq!(
+            classify_deployed_version(Some(&local), Some("2.0.0")),
+            DeployedVersionStatus::Newer
+        );
+        assert_eq!(
+            classify_deployed_version(Some(&local), None),
+            DeployedVersionStatus::Missing
+        );
+        assert_eq!(
+            classify_deployed_version(None, Some("1.0.0")),
+            DeployedVersionStatus::Unknown
+        );
+    }
+
+    #[test]
+    fn test_build_outdated_widgets_response() {
+        let widget_dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(widget_dir.path().join("src")).unwrap();
+        fs::write(
+            widget_dir.path().join("src").join("Calendar.xml"),
+            WIDGET_XML,
+        )
+        .unwrap();
+        fs::create_dir_all(widget_dir.path().join("dist").join("1.2.0")).unwrap();
+        fs::create_dir_all(widget_dir.path().join("dist").join("1.10.0")).unwrap();
+
+        let old_app = tempfile::tempdir().unwrap();
+        let new_app = tempfile::tempdir().unwrap();
+        let empty_app = tempfile::tempdir().unwrap();
+        write_deployed_mpk(old_app.path(), "Calendar.mpk", "1.2.0");
+        write_deployed_mpk(new_app.path(), "Renamed.mpk", "2.0.0");
+
+        let widgets = vec![Widget {
+            id: "w1".to_string(),
+            caption: "Calendar".to_string(),
+            path: widget_dir.path().to_string_lossy().to_string(),
+        }];
+        let apps = vec![
+            app_input(old_app.path(), "Old"),
+            app_input(new_app.path(), "New"),
+            app_input(empty_app.path(), "Empty"),
+        ];
+
+        let response = build_outdated_widgets_response(&widgets, &apps);
+        let report = &response.widgets[0];
+
+        assert_eq!(report.local_version.as_deref(), Some("1.10.0"));
+        let statuses: Vec<_> = report.apps.iter().map(|app| app.status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                DeployedVersionStatus::Older,
+                DeployedVersionStatus::Newer,
+                DeployedVersionStatus::Missing,
+            ]
+        );
+        assert_eq!(response.selected_widget_ids, vec!["w1"]);
+        assert_eq!(response.selected_app_paths, vec![apps[0].path.clone()]);
+    }
+}
@@ -0,0 +1,174 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+use super::construct_config_file_path;
+
+const MENDIX_PATHS_CONFIG_FILE: &str = "mendix_paths_config.json";
+
+/// Default recursion depth when searching app roots for projects
+pub const DEFAULT_APP_SCAN_MAX_DEPTH: usize = 4;
+
+/// Directory names never descended into while searching for apps
+pub const DEFAULT_APP_IGNORE_PATTERNS: &[&str] = &[
+    "node_modules",
+    "deployment",
+    "releases",
+    "packages",
+    "vendorlib",
+    ".git",
+    ".svn",
+    ".mendix-cache",
+];
+
+/// Days a deleted app stays in the trash before it is purged automatically
+pub const DEFAULT_TRASH_RETENTION_DAYS: u32 = 30;
+
+/// User-configured locations scanned for Studio Pro installations and Mendix apps
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MendixPathsConfig {
+    #[serde(default)]
+    pub install_roots: Vec<String>,
+    #[serde(default)]
+    pub app_roots: Vec<String>,
+    #[serde(default)]
+    pub app_scan_max_depth: Option<usize>,
+    #[serde(default)]
+    pub app_ignore_patterns: Option<Vec<String>>,
+    #[serde(default)]
+    pub trash_retention_days: Option<u32>,
+}
+
+impl MendixPathsConfig {
+    pub fn load() -> Result<Self, String> {
+        let config_path = construct_config_file_path(MENDIX_PATHS_CONFIG_FILE)?;
+
+        if !config_path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(&config_path)
+            .map_err(|e| format!("Failed to read Mendix paths config: {}", e))?;
+        serde_json::from_str(&content)
+            .map_err(|e| format!("Failed to parse Mendix paths config: {}", e))
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let config_path = construct_config_file_path(MENDIX_PATHS_CONFIG_FILE)?;
+
+        if let Some(parent) = config_path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create config directory: {}", e))?;
+        }
+
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize Mendix paths config: {}", e))?;
+        fs::write(&config_path, content)
+            .map_err(|e| format!("Failed to write Mendix paths config: {}", e))
+    }
+
+    pub fn with_install_roots(self, install_roots: Vec<String>) -> Self {
+        MendixPathsConfig {
+            install_roots: normalize_list(install_roots),
+            ..self
+        }
+    }
+
+    pub fn with_app_roots(self, app_roots: Vec<String>) -> Self {
+        MendixPathsConfig {
+            app_roots: normalize_list(app_roots),
+            ..self
+        }
+    }
+
+    pub fn with_app_scan_max_depth(self, max_depth: usize) -> Self {
+        MendixPathsConfig {
+            app_scan_max_depth: Some(max_depth),
+            ..self
+        }
+    }
+
+    pub fn with_app_ignore_patterns(self, patterns: Vec<String>) -> Self {
+        MendixPathsConfig {
+            app_ignore_patterns: Some(normalize_list(patterns)),
+            ..self
+        }
+    }
+
+    pub fn with_trash_retention_days(self, days: u32) -> Self {
+        MendixPathsConfig {
+            trash_retention_days: Some(days),
+            ..self
+        }
+    }
+
+    pub fn effective_app_scan_max_depth(&self) -> usize {
+        self.app_scan_max_depth
+            .unwrap_or(DEFAULT_APP_SCAN_MAX_DEPTH)
+    }
+
+    pub fn effective_app_ignore_patterns(&self) -> Vec<String> {
+        self.app_ignore_patterns.clone().unwrap_or_else(|| {
+            DEFAULT_APP_IGNORE_PATTERNS
+                .iter()
+                .map(|pattern| pattern.to_string())
+                .collect()
+        })
+    }
+
+    pub fn effective_trash_retention_days(&self) -> u32 {
+        self.trash_retention_days
+            .unwrap_or(DEFAULT_TRASH_RETENTION_DAYS)
+    }
+}
+
+fn normalize_list(roots: Vec<String>) -> Vec<String> {
+    roots
+        .into_iter()
+        .map(|root| root.trim().to_string())
+        .filter(|root| !root.is_empty())
+        .fold(Vec::new(), |mut acc, root| {
+            if !acc.contains(&root) {
+                acc.push(root);
+            }
+            acc
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_install_roots_trims_and_dedupes() {
+        let config = MendixPathsConfig::default().with_install_roots(vec![
+            " D:\\Mendix ".to_string(),
+            "".to_string(),
+            "D:\\Mendix".to_string(),
+            "E:\\Portable".to_string(),
+        ]);
+
+        assert_eq!(config.install_roots, vec!["D:\\Mendix", "E:\\Portable"]);
+    }
+
+    #[test]
+    fn test_deserialize_missing_fields_uses_defaults() {
+        let config: MendixPathsConfig = serde_json::from_str("{}").unwrap();
+        assert!(config.install_roots.is_empty());
+        assert!(config.app_roots.is_empty());
+        assert_eq!(
+            config.effective_app_scan_max_depth(),
+            DEFAULT_APP_SCAN_MAX_DEPTH
+        );
+        assert!(config
+            .effective_app_ignore_patterns()
+            .contains(&"node_modules".to_string()));
+    }
+
+    #[test]
+    fn test_with_app_ignore_patterns_overrides_defaults() {
+        let config =
+            MendixPathsConfig::default().with_app_ignore_patterns(vec!["archive*".to_string()]);
+
+        assert_eq!(config.effective_app_ignore_patterns(), vec!["archive*"]);
+    }
+}
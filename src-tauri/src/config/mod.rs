@@ -1,7 +1,13 @@
+mod mendix_paths;
+mod scraper;
+
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
 
+pub use mendix_paths::MendixPathsConfig;
+pub use scraper::{NetworkSettings, ScraperConfig};
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct PackageManagerConfig {
     pub npm_method: Option<String>,
@@ -9,10 +15,10 @@ pub struct PackageManagerConfig {
     pub pnpm_method: Option<String>,
 }
 
-fn construct_config_file_path() -> Result<PathBuf, String> {
+pub(crate) fn construct_config_file_path(filename: &str) -> Result<PathBuf, String> {
     dirs::config_dir()
         .ok_or_else(|| "Could not find config directory".to_string())
-        .map(|dir| dir.join("kirakiraichigo-mendix-manager").join("package_manager_config.json"))
+        .map(|dir| dir.join("kirakiraichigo-mendix-manager").join(filename))
 }
 
 impl PackageManagerConfig {
@@ -39,7 +45,7 @@ impl PackageManagerConfig {
     }
 
     pub fn load() -> Result<Self, Box<dyn std::error::Error>> {
-        let config_path = construct_config_file_path()
+        let config_path = construct_config_file_path("package_manager_config.json")
             .map_err(|e| -> Box<dyn std::error::Error> { Box::new(std::io::Error::other(e)) })?;
 
         if config_path.exists() {
@@ -54,7 +60,7 @@ impl PackageManagerConfig {
     }
 
     pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
-        let config_path = construct_config_file_path()
+        let config_path = construct_config_file_path("package_manager_config.json")
             .map_err(|e| -> Box<dyn std::error::Error> { Box::new(std::io::Error::other(e)) })?;
 
         if let Some(parent) = config_path.parent() {
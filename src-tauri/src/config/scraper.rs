@@ -0,0 +1,151 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+use super::construct_config_file_path;
+
+const SCRAPER_CONFIG_FILE: &str = "scraper_config.json";
+
+/// Hours before the downloadable versions cache is refreshed in the background
+pub const DEFAULT_VERSIONS_CACHE_TTL_HOURS: u32 = 24;
+
+/// Endpoints, proxy and certificates used to reach Mendix; unset values use the public defaults
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct NetworkSettings {
+    #[serde(default)]
+    pub marketplace_url: Option<String>,
+    #[serde(default)]
+    pub artifacts_base_url: Option<String>,
+    #[serde(default)]
+    pub proxy_url: Option<String>,
+    #[serde(default)]
+    pub proxy_username: Option<String>,
+    #[serde(default)]
+    pub proxy_password: Option<String>,
+    /// Comma-separated hosts that bypass the proxy
+    #[serde(default)]
+    pub no_proxy: Option<String>,
+    /// PEM file with extra CA certificates, trusted alongside the built-in roots
+    #[serde(default)]
+    pub ca_bundle_path: Option<String>,
+    /// Folder, typically a network share, searched for installers before downloading
+    #[serde(default)]
+    pub local_repository_path: Option<String>,
+}
+
+fn normalize_setting(value: Option<String>) -> Option<String> {
+    value
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+impl NetworkSettings {
+    /// Trim every value, clear blank ones and drop trailing slashes from URLs
+    pub fn normalized(self) -> Self {
+        let trim_url = |url: Option<String>| {
+            normalize_setting(url).map(|url| url.trim_end_matches('/').to_string())
+        };
+
+        NetworkSettings {
+            marketplace_url: trim_url(self.marketplace_url),
+            artifacts_base_url: trim_url(self.artifacts_base_url),
+            proxy_url: normalize_setting(self.proxy_url),
+            proxy_username: normalize_setting(self.proxy_username),
+            proxy_password: self.proxy_password.filter(|password| !password.is_empty()),
+            no_proxy: normalize_setting(self.no_proxy),
+            ca_bundle_path: normalize_setting(self.ca_bundle_path),
+            local_repository_path: normalize_setting(self.local_repository_path),
+        }
+    }
+
+    /// Copy handed to the UI; the proxy password never leaves the backend
+    pub fn without_password(self) -> Self {
+        NetworkSettings {
+            proxy_password: None,
+            ..self
+        }
+    }
+
+    /// Keep the stored proxy password when none is sent for the same proxy user, since
+    /// the UI never receives it; a new or cleared username drops it
+    pub fn with_stored_password(self, stored: &NetworkSettings) -> Self {
+        let keep_stored = self.proxy_password.is_none()
+            && self.proxy_username.is_some()
+            && self.proxy_username == stored.proxy_username;
+
+        NetworkSettings {
+            proxy_password: if keep_stored {
+                stored.proxy_password.clone()
+            } else {
+                self.proxy_password
+            },
+            ..self
+        }
+    }
+}
+
+/// User settings for the marketplace scraper
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScraperConfig {
+    #[serde(default)]
+    pub browser_path: Option<String>,
+    #[serde(default)]
+    pub versions_cache_ttl_hours: Option<u32>,
+    #[serde(default)]
+    pub network: NetworkSettings,
+}
+
+impl ScraperConfig {
+    pub fn load() -> Result<Self, String> {
+        let config_path = construct_config_file_path(SCRAPER_CONFIG_FILE)?;
+
+        if !config_path.exists() {
+            return Ok(Self::default());
+        }
+
+        let content = fs::read_to_string(&config_path)
+            .map_err(|e| format!("Failed to read scraper config: {}", e))?;
+        serde_json::from_str(&content).map_err(|e| format!("Failed to parse scraper config: {}", e))
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let config_path = construct_config_file_path(SCRAPER_CONFIG_FILE)?;
+
+        if let Some(parent) = config_path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create config directory: {}", e))?;
+        }
+
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize scraper config: {}", e))?;
+        fs::write(&config_path, content)
+            .map_err(|e| format!("Failed to write scraper config: {}", e))
+    }
+
+    /// Set or clear the browser executable; blank paths clear it
+    pub fn with_browser_path(self, browser_path: Option<String>) -> Self {
+        ScraperConfig {
+            browser_path: normalize_setting(browser_path),
+            ..self
+        }
+    }
+
+    /// Cache TTL in hours; 0 turns background refresh off
+    pub fn versions_cache_ttl_hours(&self) -> u32 {
+        self.versions_cache_ttl_hours
+            .unwrap_or(DEFAULT_VERSIONS_CACHE_TTL_HOURS)
+    }
+
+    pub fn with_versions_cache_ttl_hours(self, ttl_hours: u32) -> Self {
+        ScraperConfig {
+            versions_cache_ttl_hours: Some(ttl_hours),
+            ..self
+        }
+    }
+
+    pub fn with_network(self, network: NetworkSettings) -> Self {
+        ScraperConfig {
+            network: network.normalized(),
+            ..self
+        }
+    }
+}
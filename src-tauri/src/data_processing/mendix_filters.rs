@@ -1,8 +1,9 @@
 use super::*;
-use crate::mendix::{MendixApp, MendixVersion};
+use crate::mendix::{read_git_status, GitStatus, MendixApp, MendixVersion};
 use crate::storage::sort_widgets_by_order;
 use serde::Deserialize;
 use std::collections::HashSet;
+use std::path::Path;
 
 pub use super::extractors::{
     date_extractor_for_app, date_extractor_for_version, is_valid_app, is_valid_version,
@@ -81,6 +82,29 @@ pub fn process_widgets_pipeline(params: ProcessWidgetsParams) -> Result<Vec<Widg
     Ok(result)
 }
 
+/// Git state an app must be in; apps that are not git repositories only match `NotRepository`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GitStateFilter {
+    Dirty,
+    Clean,
+    Ahead,
+    Behind,
+    NotRepository,
+}
+
+fn matches_git_state(git_status: Option<&GitStatus>, filter: GitStateFilter) -> bool {
+    match (git_status, filter) {
+        (None, GitStateFilter::NotRepository) => true,
+        (None, _) => false,
+        (Some(_), GitStateFilter::NotRepository) => false,
+        (Some(status), GitStateFilter::Dirty) => status.is_dirty,
+        (Some(status), GitStateFilter::Clean) => !status.is_dirty,
+        (Some(status), GitStateFilter::Ahead) => status.ahead > 0,
+        (Some(status), GitStateFilter::Behind) => status.behind > 0,
+    }
+}
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct ProcessAppsParams {
     pub apps: Vec<MendixApp>,
@@ -89,6 +113,8 @@ pub struct ProcessAppsParams {
     pub selected_paths: Option<Vec<String>>,
     pub priority_version: Option<String>,
     pub only_valid: Option<bool>,
+    #[serde(default)]
+    pub git_state: Option<GitStateFilter>,
 }
 
 #[tauri::command]
@@ -121,6 +147,14 @@ pub fn process_apps_pipeline(params: ProcessAppsParams) -> Result<Vec<MendixApp>
         }
     }
 
+    // Scans leave the git status out, so it is read here only for apps left after the cheaper filters
+    if let Some(git_state) = params.git_state {
+        for app in result.iter_mut().filter(|app| app.git_status.is_none()) {
+            app.git_status = read_git_status(Path::new(&app.path));
+        }
+        result.retain(|app| matches_git_state(app.git_status.as_ref(), git_state));
+    }
+
     result = sort_by_version_with_date_fallback(
         result,
         version_extractor_for_app,
@@ -147,6 +181,8 @@ mod tests {
     fn create_test_version(version: &str, is_valid: bool) -> MendixVersion {
         MendixVersion {
             version: version.to_string(),
+            full_version: format!("{}.1234", version),
+            build_number: Some("1234".to_string()),
             path: format!("C:\\Program Files\\Mendix\\{}", version),
             exe_path: format!(
                 "C:\\Program Files\\Mendix\\{}\\modeler\\studiopro.exe",
@@ -154,6 +190,7 @@ mod tests {
             ),
             install_date: Some(Local::now()),
             is_valid,
+            install_root: "C:\\Program Files\\Mendix".to_string(),
         }
     }
 
@@ -162,8 +199,13 @@ mod tests {
             name: name.to_string(),
             path: format!("C:\\Users\\Test\\Mendix\\{}", name),
             version: version.map(|v| v.to_string()),
+            build_number: None,
             last_modified: Some(Local::now()),
             is_valid,
+            mpr_path: None,
+            project_id: None,
+            modules: Vec::new(),
+            git_status: None,
         }
     }
 
@@ -270,6 +312,7 @@ mod tests {
             selected_paths: None,
             priority_version: None,
             only_valid: Some(true),
+            git_state: None,
         };
 
         let result = process_apps_pipeline(params).unwrap();
@@ -293,6 +336,7 @@ mod tests {
             selected_paths: None,
             priority_version: Some("10.3.0".to_string()),
             only_valid: Some(true),
+            git_state: None,
         };
 
         let result = process_apps_pipeline(params).unwrap();
@@ -319,6 +363,7 @@ mod tests {
             selected_paths: Some(selected),
             priority_version: None,
             only_valid: Some(true),
+            git_state: None,
         };
 
         let result = process_apps_pipeline(params).unwrap();
@@ -327,4 +372,48 @@ mod tests {
         assert_eq!(result[0].name, "App3");
         assert_eq!(result[1].name, "App1");
     }
+
+    #[test]
+    fn test_process_apps_pipeline_git_state() {
+        let git_status = |is_dirty: bool, ahead: usize| GitStatus {
+            branch: Some("main".to_string()),
+            has_upstream: true,
+            ahead,
+            behind: 0,
+            is_dirty,
+        };
+        let mut apps = vec![
+            create_test_app("Dirty", Some("10.4.0"), true),
+            create_test_app("Clean", Some("10.4.0"), true),
+            create_test_app("Unpushed", Some("10.4.0"), true),
+            create_test_app("NoGit", Some("10.4.0"), true),
+        ];
+        apps[0].git_status = Some(git_status(true, 0));
+        apps[1].git_status = Some(git_status(false, 0));
+        apps[2].git_status = Some(git_status(false, 2));
+
+        let names_for = |git_state: GitStateFilter| -> Vec<String> {
+            let params = ProcessAppsParams {
+                apps: apps.clone(),
+                search_term: None,
+                target_version: None,
+                selected_paths: None,
+                priority_version: None,
+                only_valid: Some(true),
+                git_state: Some(git_state),
+            };
+            let mut names: Vec<String> = process_apps_pipeline(params)
+                .unwrap()
+                .into_iter()
+                .map(|app| app.name)
+                .collect();
+            names.sort();
+            names
+        };
+
+        assert_eq!(names_for(GitStateFilter::Dirty), vec!["Dirty"]);
+        assert_eq!(names_for(GitStateFilter::Clean), vec!["Clean", "Unpushed"]);
+        assert_eq!(names_for(GitStateFilter::Ahead), vec!["Unpushed"]);
+        assert_eq!(names_for(GitStateFilter::NotRepository), vec!["NoGit"]);
+    }
 }
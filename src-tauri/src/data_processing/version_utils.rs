@@ -213,6 +213,8 @@ mod tests {
     fn create_test_installed_version(version: &str) -> MendixVersion {
         MendixVersion {
             version: version.to_string(),
+            full_version: format!("{}.1234", version),
+            build_number: Some("1234".to_string()),
             path: format!("C:\\Program Files\\Mendix\\{}", version),
             exe_path: format!(
                 "C:\\Program Files\\Mendix\\{}\\modeler\\studiopro.exe",
@@ -220,6 +222,7 @@ mod tests {
             ),
             install_date: Some(Local::now()),
             is_valid: true,
+            install_root: "C:\\Program Files\\Mendix".to_string(),
         }
     }
 
@@ -239,6 +242,9 @@ mod tests {
             is_mts,
             is_beta,
             is_latest: false,
+            first_seen_at: None,
+            build_number: None,
+            artifacts: Vec::new(),
         }
     }
 
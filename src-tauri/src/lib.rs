@@ -16,18 +16,43 @@ mod widget_preview;
 
 pub use config::PackageManagerConfig;
 pub use mendix::{
-    delete_mendix_app, get_apps_by_version, get_installed_mendix_apps,
-    get_installed_mendix_versions, launch_studio_pro, uninstall_studio_pro_and_wait, MendixApp,
-    MendixVersion,
+    cleanup_app_caches, delete_mendix_app, get_app_git_status, get_app_widget_inventory,
+    get_apps_by_version, get_apps_disk_usage, get_installed_mendix_apps,
+    get_installed_mendix_versions, get_mendix_app_scan_settings, get_mendix_install_roots,
+    get_mpr_metadata, get_running_studio_pro_instances, get_version_usage_report,
+    launch_studio_pro, list_trashed_apps, open_app_in_studio_pro, purge_trashed_apps,
+    restore_trashed_app, save_mendix_app_scan_settings, save_mendix_install_roots,
+    save_trash_retention_days, uninstall_studio_pro_and_wait, AppCleanupResult, AppDiskUsage,
+    AppScanSettings, AppWidgetInventory, CacheCategory, CategoryUsage, CleanupSummary,
+    InstallResult, InstallRoot, MendixApp, MendixChangeEvent, MendixVersion, MprMetadata,
+    OpenAppResult, OrphanedApp, PackagedWidget, RunningStudioPro, TrashedApp, UnusedVersion,
+    VersionUsageReport, WidgetPackage,
 };
 pub use package_manager::{batch_install_widgets, BatchInstallSummary};
 pub use utils::extract_folder_name_from_path;
 pub use web_scraper::{
-    download_and_install_mendix_version, get_downloadable_versions_from_datagrid, BuildInfo,
-    DownloadProgress, DownloadableVersion,
+    cancel_mendix_download, detect_browser_executable, download_and_install_mendix_version,
+    enqueue_mendix_downloads, export_downloadable_versions_catalogue, get_download_queue,
+    get_downloadable_versions_from_datagrid, get_network_settings, get_release_notes,
+    get_release_notes_changelog, get_selector_profile, get_versions_cache_status,
+    import_downloadable_versions_catalogue, install_mendix_version_and_wait,
+    list_cached_installers, move_download_queue_item, prune_installer_cache,
+    remove_from_download_queue, retry_download_queue_item, save_browser_path,
+    save_network_settings, save_selector_profile, save_versions_cache_ttl,
+    scrape_all_downloadable_versions, set_download_queue_concurrency, verify_cached_installers,
+    ArtifactPlatform, BrowserDetection, BrowserSource, BuildInfo, CachedInstaller, CatalogueImport,
+    ChangelogEntry, ChangelogSection, DatagridScrapeSummary, DownloadProgress, DownloadableVersion,
+    DownloadableVersionsPage, InstallerCachePruneResult, InstallerVerification, NewVersionNotice,
+    PlatformArtifact, ReleaseNoteCategory, ReleaseNoteSection, ReleaseNotesChangelog,
+    SelectorProfile, VersionReleaseNotes, VersionsCacheStatus, DOWNLOADABLE_VERSIONS_PAGE_EVENT,
+    DOWNLOAD_PROGRESS_EVENT, DOWNLOAD_QUEUE_EVENT, NEW_VERSIONS_EVENT,
 };
 
-pub use build_deploy::{check_multiple_dist_exists, create_catastrophic_error_result, validate_and_build_deploy, validate_and_deploy_only};
+pub use build_deploy::{
+    check_multiple_dist_exists, create_catastrophic_error_result, find_outdated_widgets,
+    validate_and_build_deploy, validate_and_deploy_only, AppWidgetVersion, DeployedVersionStatus,
+    OutdatedWidgetsResponse, WidgetVersionReport,
+};
 pub use storage::{
     add_widget_and_save, clear_downloadable_versions_cache, delete_widget_and_save,
     load_downloadable_versions_cache, load_from_storage, load_widgets_ordered,
@@ -59,6 +84,7 @@ pub use state::{
     get_version_loading_state, has_selection, init_selection_from_storage, is_selected,
     is_version_busy, remove_from_selection, remove_from_selection_with_save, set_selection,
     set_version_operation, toggle_selection, toggle_selection_with_save, AppState,
+    DownloadQueueItem, DownloadQueueState, DownloadQueueStatus,
 };
 
 pub use business_logic::{
@@ -79,16 +105,42 @@ pub fn run() {
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_os::init())
         .manage(AppState::default())
+        .setup(|app| {
+            if let Err(e) = mendix::start_mendix_watcher(app.handle().clone()) {
+                println!("[Watcher] {}", e);
+            }
+            if let Err(e) = web_scraper::resume_download_queue(app.handle()) {
+                println!("[Download Queue] {}", e);
+            }
+            web_scraper::start_versions_cache_refresh(app.handle().clone());
+            Ok(())
+        })
         .invoke_handler(tauri::generate_handler![
             // ================================================================
             // Mendix version management
             // ================================================================
             get_installed_mendix_versions,
             launch_studio_pro,
+            open_app_in_studio_pro,
+            get_running_studio_pro_instances,
+            get_version_usage_report,
             uninstall_studio_pro_and_wait,
             delete_mendix_app,
             get_apps_by_version,
             get_installed_mendix_apps,
+            get_mendix_install_roots,
+            save_mendix_install_roots,
+            get_mendix_app_scan_settings,
+            save_mendix_app_scan_settings,
+            get_mpr_metadata,
+            get_app_git_status,
+            get_app_widget_inventory,
+            list_trashed_apps,
+            restore_trashed_app,
+            purge_trashed_apps,
+            save_trash_retention_days,
+            get_apps_disk_usage,
+            cleanup_app_caches,
             // ================================================================
             // Widget management
             // ================================================================
@@ -100,6 +152,30 @@ pub fn run() {
             // ================================================================
             get_downloadable_versions_from_datagrid,
             download_and_install_mendix_version,
+            install_mendix_version_and_wait,
+            cancel_mendix_download,
+            list_cached_installers,
+            verify_cached_installers,
+            prune_installer_cache,
+            enqueue_mendix_downloads,
+            get_download_queue,
+            remove_from_download_queue,
+            move_download_queue_item,
+            retry_download_queue_item,
+            set_download_queue_concurrency,
+            get_release_notes,
+            get_release_notes_changelog,
+            detect_browser_executable,
+            save_browser_path,
+            scrape_all_downloadable_versions,
+            get_versions_cache_status,
+            save_versions_cache_ttl,
+            get_network_settings,
+            save_network_settings,
+            get_selector_profile,
+            save_selector_profile,
+            export_downloadable_versions_catalogue,
+            import_downloadable_versions_catalogue,
             // ================================================================
             // Widget preview & build
             // ================================================================
@@ -146,6 +222,7 @@ pub fn run() {
             validate_and_deploy_only,
             check_multiple_dist_exists,
             create_catastrophic_error_result,
+            find_outdated_widgets,
             // ================================================================
             // Data processing pipelines
             // ================================================================
@@ -0,0 +1,388 @@
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use super::scanner::is_mendix_app_directory;
+
+const NODE_MODULES_DIR: &str = "node_modules";
+
+/// Folder of built widget packages inside an app
+const WIDGETS_DIR: &str = "widgets";
+
+/// JavaScript action sources; their `node_modules` are needed to build the app
+const JAVASCRIPT_SOURCE_DIR: &str = "javascriptsource";
+
+/// Regenerable folders that Studio Pro or npm recreate on the next build
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CacheCategory {
+    Deployment,
+    MendixCache,
+    Packages,
+    VendorlibTemp,
+    NodeModules,
+}
+
+pub const ALL_CACHE_CATEGORIES: [CacheCategory; 5] = [
+    CacheCategory::Deployment,
+    CacheCategory::MendixCache,
+    CacheCategory::Packages,
+    CacheCategory::VendorlibTemp,
+    CacheCategory::NodeModules,
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategoryUsage {
+    pub category: CacheCategory,
+    pub bytes: u64,
+    pub paths: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppDiskUsage {
+    pub app_path: String,
+    pub total_bytes: u64,
+    pub reclaimable_bytes: u64,
+    pub categories: Vec<CategoryUsage>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppCleanupResult {
+    pub app_path: String,
+    pub reclaimed_bytes: u64,
+    pub removed_paths: Vec<String>,
+    pub errors: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CleanupSummary {
+    pub results: Vec<AppCleanupResult>,
+    pub total_reclaimed_bytes: u64,
+    pub failure_count: usize,
+}
+
+/// Fixed location of a category relative to the app, if it has one
+fn category_relative_path(category: CacheCategory) -> Option<PathBuf> {
+    match category {
+        CacheCategory::Deployment => Some(PathBuf::from("deployment")),
+        CacheCategory::MendixCache => Some(PathBuf::from(".mendix-cache")),
+        CacheCategory::Packages => Some(PathBuf::from("packages")),
+        CacheCategory::VendorlibTemp => Some(Path::new("vendorlib").join("temp")),
+        CacheCategory::NodeModules => None,
+    }
+}
+
+/// A pluggable widget source package, identified by `widgetName` in its `package.json`
+fn is_widget_package_dir(dir: &Path) -> bool {
+    fs::read_to_string(dir.join("package.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .is_some_and(|package| package.get("widgetName").is_some())
+}
+
+/// `node_modules` of widget build output: directly in a `widgets/*` folder or in a widget
+/// source package
+fn is_widget_node_modules(app_path: &Path, node_modules: &Path) -> bool {
+    let Some(package_dir) = node_modules.parent() else {
+        return false;
+    };
+
+    package_dir.parent() == Some(app_path.join(WIDGETS_DIR).as_path())
+        || is_widget_package_dir(package_dir)
+}
+
+/// Find widget `node_modules` folders in the app without descending into them
+fn find_node_modules_dirs(app_path: &Path) -> Vec<PathBuf> {
+    let skipped_dirs: Vec<PathBuf> = ALL_CACHE_CATEGORIES
+        .iter()
+        .filter_map(|category| category_relative_path(*category))
+        .chain([PathBuf::from(JAVASCRIPT_SOURCE_DIR)])
+        .map(|relative| app_path.join(relative))
+        .collect();
+
+    let mut found = Vec::new();
+    let mut walker = WalkDir::new(app_path).min_depth(1).into_iter();
+
+    while let Some(entry) = walker.next() {
+        let Ok(entry) = entry else { continue };
+        if !entry.file_type().is_dir() {
+            continue;
+        }
+        if skipped_dirs.iter().any(|dir| dir == entry.path()) {
+            walker.skip_current_dir();
+            continue;
+        }
+        if entry.file_name() == NODE_MODULES_DIR {
+            if is_widget_node_modules(app_path, entry.path()) {
+                found.push(entry.path().to_path_buf());
+            }
+            walker.skip_current_dir();
+        }
+    }
+
+    found
+}
+
+fn find_category_dirs(app_path: &Path, category: CacheCategory) -> Vec<PathBuf> {
+    match category_relative_path(category) {
+        Some(relative) => Some(app_path.join(relative))
+            .filter(|path| path.is_dir())
+            .into_iter()
+            .collect(),
+        None => find_node_modules_dirs(app_path),
+    }
+}
+
+/// Total size of all files below a directory, without following symlinks
+pub fn calculate_directory_size(dir: &Path) -> u64 {
+    WalkDir::new(dir)
+        .into_iter()
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| entry.metadata().ok())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+fn to_string_paths(paths: &[PathBuf]) -> Vec<String> {
+    paths
+        .iter()
+        .map(|path| path.to_string_lossy().to_string())
+        .collect()
+}
+
+fn verify_app_directory(app_path: &Path) -> Result<(), String> {
+    if is_mendix_app_directory(app_path) {
+        Ok(())
+    } else {
+        Err(format!("Not a Mendix app: {}", app_path.display()))
+    }
+}
+
+fn calculate_app_disk_usage(app_path: &str) -> AppDiskUsage {
+    let path = Path::new(app_path);
+
+    if let Err(error) = verify_app_directory(path) {
+        return AppDiskUsage {
+            app_path: app_path.to_string(),
+            total_bytes: 0,
+            reclaimable_bytes: 0,
+            categories: Vec::new(),
+            error: Some(error),
+        };
+    }
+
+    let categories: Vec<CategoryUsage> = ALL_CACHE_CATEGORIES
+        .iter()
+        .map(|category| {
+            let dirs = find_category_dirs(path, *category);
+            CategoryUsage {
+                category: *category,
+                bytes: dirs.iter().map(|dir| calculate_directory_size(dir)).sum(),
+                paths: to_string_paths(&dirs),
+            }
+        })
+        .collect();
+
+    AppDiskUsage {
+        app_path: app_path.to_string(),
+        total_bytes: calculate_directory_size(path),
+        reclaimable_bytes: categories.iter().map(|usage| usage.bytes).sum(),
+        categories,
+        error: None,
+    }
+}
+
+fn cleanup_app(app_path: &str, categories: &[CacheCategory]) -> AppCleanupResult {
+    let path = Path::new(app_path);
+    let mut result = AppCleanupResult {
+        app_path: app_path.to_string(),
+        reclaimed_bytes: 0,
+        removed_paths: Vec::new(),
+        errors: Vec::new(),
+    };
+
+    if let Err(error) = verify_app_directory(path) {
+        result.errors.push(error);
+        return result;
+    }
+
+    let dirs = categories
+        .iter()
+        .flat_map(|category| find_category_dirs(path, *category));
+
+    for dir in dirs {
+        let size = calculate_directory_size(&dir);
+        match fs::remove_dir_all(&dir) {
+            Ok(()) => {
+                result.reclaimed_bytes += size;
+                result.removed_paths.push(dir.to_string_lossy().to_string());
+            }
+            Err(e) => result
+                .errors
+                .push(format!("Failed to remove {}: {}", dir.display(), e)),
+        }
+    }
+
+    result
+}
+
+#[tauri::command]
+pub fn get_apps_disk_usage(app_paths: Vec<String>) -> Result<Vec<AppDiskUsage>, String> {
+    Ok(app_paths
+        .par_iter()
+        .map(|app_path| calculate_app_disk_usage(app_path))
+        .collect())
+}
+
+/// Remove regenerable folders from the given apps; all categories when none are chosen
+#[tauri::command]
+pub fn cleanup_app_caches(
+    app_paths: Vec<String>,
+    categories: Option<Vec<CacheCategory>>,
+) -> Result<CleanupSummary, String> {
+    let categories = match categories {
+        Some(categories) if !categories.is_empty() => categories,
+        _ => ALL_CACHE_CATEGORIES.to_vec(),
+    };
+
+    let results: Vec<AppCleanupResult> = app_paths
+        .par_iter()
+        .map(|app_path| cleanup_app(app_path, &categories))
+        .collect();
+
+    let total_reclaimed_bytes = results.iter().map(|r| r.reclaimed_bytes).sum();
+    let failure_count = results.iter().filter(|r| !r.errors.is_empty()).count();
+
+    println!(
+        "[Cache Cleanup] Reclaimed {} bytes from {} apps ({} with errors)",
+        total_reclaimed_bytes,
+        results.len(),
+        failure_count
+    );
+
+    Ok(CleanupSummary {
+        results,
+        total_reclaimed_bytes,
+        failure_count,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_file(path: &Path, size: usize) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, vec![0u8; size]).unwrap();
+    }
+
+    fn create_app(dir: &Path) {
+        write_file(&dir.join("App.mpr"), 100);
+        write_file(&dir.join("deployment").join("model.mdp"), 1000);
+        write_file(&dir.join(".mendix-cache").join("cache.bin"), 200);
+        write_file(&dir.join("vendorlib").join("temp").join("a.jar"), 300);
+        write_file(&dir.join("vendorlib").join("keep.jar"), 50);
+        write_file(
+            &dir.join("javascriptsource")
+                .join("mod")
+                .join("actions")
+                .join("node_modules")
+                .join("pkg")
+                .join("index.js"),
+            400,
+        );
+
+        let widget_source = dir.join("myPluggableWidgets").join("chart");
+        fs::create_dir_all(&widget_source).unwrap();
+        fs::write(
+            widget_source.join("package.json"),
+            r#"{ "name": "chart", "widgetName": "Chart" }"#,
+        )
+        .unwrap();
+        write_file(
+            &widget_source
+                .join("node_modules")
+                .join("pkg")
+                .join("index.js"),
+            250,
+        );
+        write_file(
+            &dir.join("tools")
+                .join("node_modules")
+                .join("pkg")
+                .join("index.js"),
+            70,
+        );
+    }
+
+    fn usage_of(usage: &AppDiskUsage, category: CacheCategory) -> u64 {
+        usage
+            .categories
+            .iter()
+            .find(|entry| entry.category == category)
+            .map(|entry| entry.bytes)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_calculate_app_disk_usage() {
+        let app = tempfile::tempdir().unwrap();
+        create_app(app.path());
+
+        let usage = calculate_app_disk_usage(&app.path().to_string_lossy());
+
+        assert_eq!(usage.reclaimable_bytes, 1750);
+        assert_eq!(usage_of(&usage, CacheCategory::Deployment), 1000);
+        assert_eq!(usage_of(&usage, CacheCategory::VendorlibTemp), 300);
+        assert_eq!(usage_of(&usage, CacheCategory::NodeModules), 250);
+        assert_eq!(usage_of(&usage, CacheCategory::Packages), 0);
+    }
+
+    #[test]
+    fn test_cleanup_only_removes_selected_categories() {
+        let app = tempfile::tempdir().unwrap();
+        create_app(app.path());
+
+        let summary = cleanup_app_caches(
+            vec![app.path().to_string_lossy().to_string()],
+            Some(vec![CacheCategory::Deployment, CacheCategory::NodeModules]),
+        )
+        .unwrap();
+
+        assert_eq!(summary.total_reclaimed_bytes, 1250);
+        assert_eq!(summary.failure_count, 0);
+        assert!(!app.path().join("deployment").exists());
+        assert!(!app
+            .path()
+            .join("myPluggableWidgets")
+            .join("chart")
+            .join("node_modules")
+            .exists());
+        assert!(app
+            .path()
+            .join("javascriptsource")
+            .join("mod")
+            .join("actions")
+            .join("node_modules")
+            .exists());
+        assert!(app.path().join(".mendix-cache").exists());
+        assert!(app.path().join("vendorlib").join("keep.jar").exists());
+        assert!(app.path().join("App.mpr").exists());
+    }
+
+    #[test]
+    fn test_cleanup_rejects_non_app_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        write_file(&dir.path().join("deployment").join("file"), 10);
+
+        let summary =
+            cleanup_app_caches(vec![dir.path().to_string_lossy().to_string()], None).unwrap();
+
+        assert_eq!(summary.failure_count, 1);
+        assert!(dir.path().join("deployment").exists());
+    }
+}
@@ -1,16 +1,30 @@
-use std::fs;
-use std::path::Path;
+use chrono::Utc;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
-use super::models::{MendixApp, MendixVersion, UninstallResult};
+use crate::config::MendixPathsConfig;
+
+use super::models::{AppScanSettings, MendixApp, MendixVersion, OpenAppResult, UninstallResult};
 use super::paths::{
-    construct_exe_path, construct_uninstall_path, get_mendix_apps_directory, MENDIX_DATA_DIR,
-    MENDIX_INSTALL_DIR,
+    construct_uninstall_path, get_app_roots, get_install_roots, get_mendix_data_directory,
+    is_same_path, InstallRoot,
+};
+use super::processes::{
+    ensure_app_not_in_use, ensure_install_not_in_use, find_running_studio_pro, SystemProcessLister,
 };
 use super::scanner::{
-    filter_apps_by_version, filter_valid_apps, filter_valid_versions, process_mendix_apps,
-    process_mendix_versions, scan_mendix_directory, sort_apps_by_last_modified,
-    sort_versions_by_descending,
+    filter_apps_by_version, filter_valid_apps, filter_valid_versions, find_closest_newer_version,
+    find_installed_version, find_mpr_file, format_full_version, matches_version_query,
+    scan_app_roots, scan_install_directory, scan_install_roots, sort_apps_by_last_modified,
+    sort_versions_by_descending, AppScanOptions, IgnorePatterns,
+};
+use super::trash::{
+    find_trash_directory, get_app_trash_directories, get_app_trash_directory,
+    list_all_trash_entries, move_app_to_trash, purge_expired_trash_entries, purge_trash_entry,
+    restore_app_from_trash, verify_deletable_app, TrashedApp,
+};
+use super::watcher::{
+    reload_watched_roots, subscribe_mendix_changes, wait_for_change, MendixChangeEvent,
 };
 
 #[cfg(target_os = "windows")]
@@ -33,68 +47,303 @@ fn execute_command(exe_path: &str, args: &[&str]) -> Result<(), String> {
     Ok(())
 }
 
-/// Remove a directory recursively
-fn remove_directory(path: &str) -> Result<(), String> {
-    let path_obj = Path::new(path);
+/// Resolve the install roots from the persisted configuration and environment
+pub(super) fn load_install_roots() -> Vec<InstallRoot> {
+    let config = MendixPathsConfig::load().unwrap_or_default();
+    get_install_roots(&config.install_roots)
+}
+
+/// Resolve the app roots from the persisted configuration and environment
+pub(super) fn load_app_roots() -> Vec<InstallRoot> {
+    let config = MendixPathsConfig::load().unwrap_or_default();
+    get_app_roots(&config.app_roots)
+}
 
-    if !path_obj.exists() {
-        return Err("Directory does not exist".to_string());
+/// Scan all app roots for Mendix apps
+pub(super) fn scan_installed_apps() -> Vec<MendixApp> {
+    let config = MendixPathsConfig::load().unwrap_or_default();
+    scan_app_roots(
+        &get_app_roots(&config.app_roots),
+        &app_scan_options(&config),
+    )
+}
+
+fn app_scan_options(config: &MendixPathsConfig) -> AppScanOptions {
+    AppScanOptions {
+        max_depth: config.effective_app_scan_max_depth(),
+        ignore_patterns: IgnorePatterns::new(&config.effective_app_ignore_patterns()),
     }
+}
 
-    if !path_obj.is_dir() {
-        return Err("Path is not a directory".to_string());
+fn create_app_scan_settings(config: &MendixPathsConfig) -> AppScanSettings {
+    AppScanSettings {
+        roots: get_app_roots(&config.app_roots),
+        max_depth: config.effective_app_scan_max_depth(),
+        ignore_patterns: config.effective_app_ignore_patterns(),
     }
+}
 
-    fs::remove_dir_all(path_obj).map_err(|e| format!("Failed to delete directory: {}", e))
+/// Scan all install roots for Studio Pro installations
+pub(super) fn scan_installed_versions() -> Vec<MendixVersion> {
+    scan_install_roots(&load_install_roots())
 }
 
 /// Start the uninstaller for a Mendix version
-fn uninstall_studio_pro(version: &str) -> Result<(), String> {
-    construct_uninstall_path(MENDIX_DATA_DIR, version)
-        .filter(|path| Path::new(path).exists())
-        .ok_or_else(|| format!("Uninstaller not found for version {}", version))
-        .and_then(|uninstall_path| execute_command(&uninstall_path, &["/SILENT"]))
+fn uninstall_studio_pro(version: &str, target: &MendixVersion) -> Result<(), String> {
+    construct_uninstall_path(
+        &get_mendix_data_directory(),
+        &target.full_version,
+        Some(Path::new(&target.path)),
+    )
+    .ok_or_else(|| format!("Uninstaller not found for version {}", version))
+    .and_then(|uninstall_path| execute_command(&uninstall_path.to_string_lossy(), &["/SILENT"]))
+}
+
+/// Check whether the uninstall target is still present in the scan
+fn is_still_installed(target: &MendixVersion) -> bool {
+    scan_installed_versions()
+        .iter()
+        .any(|v| v.path == target.path)
+}
+
+/// Installed version matching the query, inside `install_dir` when one was requested
+fn find_installation(version: &str, install_dir: Option<&Path>) -> Option<MendixVersion> {
+    let installed = scan_installed_versions();
+
+    match install_dir {
+        Some(install_dir) => installed
+            .into_iter()
+            .find(|candidate| {
+                is_same_path(Path::new(&candidate.path), install_dir)
+                    && matches_version_query(
+                        version,
+                        &candidate.version,
+                        candidate.build_number.as_deref(),
+                    )
+            })
+            .or_else(|| scan_install_directory(install_dir, version)),
+        None => find_installed_version(&installed, version).cloned(),
+    }
+}
+
+/// Wait for a freshly installed version to show up in the scan
+pub(crate) async fn wait_for_installed_version(
+    version: &str,
+    install_dir: Option<&Path>,
+    timeout: std::time::Duration,
+) -> Option<MendixVersion> {
+    let mut changes = subscribe_mendix_changes();
+    let query = version.to_string();
+    let target_dir = install_dir.map(Path::to_path_buf);
+
+    wait_for_change(
+        &mut changes,
+        timeout,
+        install_dir,
+        |change| {
+            matches!(change, MendixChangeEvent::VersionAdded { version: added }
+                if matches_version_query(version, &added.version, added.build_number.as_deref()))
+        },
+        move || find_installation(&query, target_dir.as_deref()).is_some(),
+    )
+    .await;
+
+    find_installation(version, install_dir)
 }
 
 #[tauri::command]
 pub fn get_installed_mendix_versions() -> Result<Vec<MendixVersion>, String> {
-    scan_mendix_directory(MENDIX_INSTALL_DIR)
-        .map(process_mendix_versions)
-        .map(filter_valid_versions)
-        .map(sort_versions_by_descending)
+    Ok(sort_versions_by_descending(filter_valid_versions(
+        scan_installed_versions(),
+    )))
 }
 
 #[tauri::command]
-pub fn get_installed_mendix_apps() -> Result<Vec<MendixApp>, String> {
-    let mendix_dir = get_mendix_apps_directory()?;
+pub fn get_mendix_install_roots() -> Result<Vec<InstallRoot>, String> {
+    Ok(load_install_roots())
+}
+
+#[tauri::command]
+pub fn save_mendix_install_roots(install_roots: Vec<String>) -> Result<Vec<InstallRoot>, String> {
+    let config = MendixPathsConfig::load()
+        .unwrap_or_default()
+        .with_install_roots(install_roots);
+    config.save()?;
+    reload_watched_roots();
+
+    Ok(get_install_roots(&config.install_roots))
+}
 
-    scan_mendix_directory(&mendix_dir)
-        .map(process_mendix_apps)
-        .map(filter_valid_apps)
-        .map(sort_apps_by_last_modified)
+#[tauri::command]
+pub fn get_installed_mendix_apps() -> Result<Vec<MendixApp>, String> {
+    Ok(sort_apps_by_last_modified(filter_valid_apps(
+        scan_installed_apps(),
+    )))
 }
 
 #[tauri::command]
 pub fn get_apps_by_version(version: String) -> Result<Vec<MendixApp>, String> {
-    let mendix_dir = get_mendix_apps_directory()?;
+    Ok(filter_apps_by_version(
+        filter_valid_apps(scan_installed_apps()),
+        &version,
+    ))
+}
+
+#[tauri::command]
+pub fn get_mendix_app_scan_settings() -> Result<AppScanSettings, String> {
+    Ok(create_app_scan_settings(
+        &MendixPathsConfig::load().unwrap_or_default(),
+    ))
+}
+
+#[tauri::command]
+pub fn save_mendix_app_scan_settings(
+    app_roots: Vec<String>,
+    max_depth: Option<usize>,
+    ignore_patterns: Option<Vec<String>>,
+) -> Result<AppScanSettings, String> {
+    let config = MendixPathsConfig::load()
+        .unwrap_or_default()
+        .with_app_roots(app_roots);
+    let config = match max_depth {
+        Some(depth) => config.with_app_scan_max_depth(depth),
+        None => config,
+    };
+    let config = match ignore_patterns {
+        Some(patterns) => config.with_app_ignore_patterns(patterns),
+        None => config,
+    };
+    config.save()?;
+    reload_watched_roots();
 
-    scan_mendix_directory(&mendix_dir)
-        .map(process_mendix_apps)
-        .map(filter_valid_apps)
-        .map(|apps| filter_apps_by_version(apps, &version))
+    Ok(create_app_scan_settings(&config))
 }
 
 #[tauri::command]
 pub fn launch_studio_pro(version: String) -> Result<(), String> {
-    construct_exe_path(MENDIX_INSTALL_DIR, &version)
+    let installed = scan_installed_versions();
+
+    find_installed_version(&installed, &version)
+        .map(|installed_version| installed_version.exe_path.clone())
         .filter(|path| Path::new(path).exists())
         .ok_or_else(|| format!("Studio Pro executable not found for version {}", version))
         .and_then(|exe_path| execute_command(&exe_path, &[]))
 }
 
+/// Open an app's project file with the Studio Pro build it was last saved with
+#[tauri::command]
+pub fn open_app_in_studio_pro(app: MendixApp) -> Result<OpenAppResult, String> {
+    let version = app
+        .version
+        .as_deref()
+        .ok_or_else(|| format!("Could not determine the Mendix version of {}", app.name))?;
+    let app_version = format_full_version(version, app.build_number.as_deref());
+
+    let mpr_path = app
+        .mpr_path
+        .clone()
+        .map(std::path::PathBuf::from)
+        .or_else(|| find_mpr_file(Path::new(&app.path)))
+        .filter(|path| path.exists())
+        .ok_or_else(|| format!("Project file not found in {}", app.path))?;
+
+    let installed = scan_installed_versions();
+
+    match find_installed_version(&installed, &app_version) {
+        Some(matched) => {
+            if !Path::new(&matched.exe_path).exists() {
+                return Err(format!(
+                    "Studio Pro executable not found for version {}",
+                    matched.full_version
+                ));
+            }
+            execute_command(&matched.exe_path, &[&mpr_path.to_string_lossy()])?;
+
+            Ok(OpenAppResult {
+                launched: true,
+                app_version,
+                matched_version: Some(matched.clone()),
+                closest_newer_version: None,
+                version_to_download: None,
+            })
+        }
+        None => Ok(OpenAppResult {
+            launched: false,
+            closest_newer_version: find_closest_newer_version(&installed, &app_version).cloned(),
+            version_to_download: Some(app_version.clone()),
+            app_version,
+            matched_version: None,
+        }),
+    }
+}
+
+/// Trash directories of the configured app roots
+fn app_trash_directories(config: &MendixPathsConfig) -> Vec<PathBuf> {
+    get_app_trash_directories(&get_app_roots(&config.app_roots))
+}
+
+/// Purge trash entries past the configured retention period
+fn purge_expired_trash(trash_dirs: &[PathBuf], config: &MendixPathsConfig) {
+    for trash_dir in trash_dirs {
+        purge_expired_trash_entries(
+            trash_dir,
+            config.effective_trash_retention_days(),
+            Utc::now(),
+        );
+    }
+}
+
+#[tauri::command]
+pub fn delete_mendix_app(app_path: String) -> Result<TrashedApp, String> {
+    let config = MendixPathsConfig::load().unwrap_or_default();
+    let roots = get_app_roots(&config.app_roots);
+    let app_path = Path::new(&app_path);
+    verify_deletable_app(app_path, &roots)?;
+    ensure_app_not_in_use(&find_running_studio_pro(&SystemProcessLister), app_path)?;
+
+    purge_expired_trash(&app_trash_directories(&config), &config);
+    let trash_dir = get_app_trash_directory(app_path, &roots)?;
+    move_app_to_trash(app_path, &trash_dir, Utc::now())
+}
+
+#[tauri::command]
+pub fn list_trashed_apps() -> Result<Vec<TrashedApp>, String> {
+    let config = MendixPathsConfig::load().unwrap_or_default();
+    let trash_dirs = app_trash_directories(&config);
+    purge_expired_trash(&trash_dirs, &config);
+    Ok(list_all_trash_entries(&trash_dirs))
+}
+
+#[tauri::command]
+pub fn restore_trashed_app(trash_id: String) -> Result<TrashedApp, String> {
+    let trash_dirs = app_trash_directories(&MendixPathsConfig::load().unwrap_or_default());
+    restore_app_from_trash(find_trash_directory(&trash_dirs, &trash_id)?, &trash_id)
+}
+
+/// Permanently delete the given trash entries, or all of them when no ids are passed
+#[tauri::command]
+pub fn purge_trashed_apps(trash_ids: Option<Vec<String>>) -> Result<Vec<TrashedApp>, String> {
+    let trash_dirs = app_trash_directories(&MendixPathsConfig::load().unwrap_or_default());
+    let ids = trash_ids.unwrap_or_else(|| {
+        list_all_trash_entries(&trash_dirs)
+            .into_iter()
+            .map(|entry| entry.id)
+            .collect()
+    });
+
+    ids.iter()
+        .map(|id| purge_trash_entry(find_trash_directory(&trash_dirs, id)?, id))
+        .collect()
+}
+
 #[tauri::command]
-pub fn delete_mendix_app(app_path: String) -> Result<(), String> {
-    remove_directory(&app_path)
+pub fn save_trash_retention_days(days: u32) -> Result<u32, String> {
+    let config = MendixPathsConfig::load()
+        .unwrap_or_default()
+        .with_trash_retention_days(days);
+    config.save()?;
+
+    Ok(config.effective_trash_retention_days())
 }
 
 #[tauri::command]
@@ -103,38 +352,34 @@ pub async fn uninstall_studio_pro_and_wait(
     timeout_seconds: Option<u64>,
 ) -> Result<UninstallResult, String> {
     let timeout = timeout_seconds.unwrap_or(60);
+    let target = find_installed_version(&scan_installed_versions(), &version)
+        .cloned()
+        .ok_or_else(|| format!("Mendix Studio Pro {} is not installed", version))?;
 
-    uninstall_studio_pro(&version)?;
+    ensure_install_not_in_use(
+        &find_running_studio_pro(&SystemProcessLister),
+        Path::new(&target.path),
+    )?;
 
-    let poll_interval = std::time::Duration::from_secs(1);
-    let start_time = std::time::Instant::now();
-    let timeout_duration = std::time::Duration::from_secs(timeout);
+    let mut changes = subscribe_mendix_changes();
+    uninstall_studio_pro(&version, &target)?;
 
-    loop {
-        let folder_exists = scan_mendix_directory(MENDIX_INSTALL_DIR)
-            .map(|entries| {
-                entries
-                    .into_iter()
-                    .any(|(dir_name, _)| dir_name.starts_with(&version))
-            })
-            .unwrap_or(false);
-
-        if !folder_exists {
-            return Ok(UninstallResult {
-                success: true,
-                version,
-                timed_out: false,
-            });
-        }
-
-        if start_time.elapsed() >= timeout_duration {
-            return Ok(UninstallResult {
-                success: false,
-                version,
-                timed_out: true,
-            });
-        }
+    let remaining = target.clone();
+    let removed = wait_for_change(
+        &mut changes,
+        std::time::Duration::from_secs(timeout),
+        Some(Path::new(&target.path)),
+        |change| match change {
+            MendixChangeEvent::VersionRemoved { version: removed } => removed.path == target.path,
+            _ => false,
+        },
+        move || !is_still_installed(&remaining),
+    )
+    .await;
 
-        tokio::time::sleep(poll_interval).await;
-    }
+    Ok(UninstallResult {
+        success: removed,
+        version,
+        timed_out: !removed,
+    })
 }
@@ -0,0 +1,148 @@
+use git2::{Branch, Repository, StatusOptions};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+const GIT_DIR: &str = ".git";
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GitStatus {
+    /// `None` when HEAD is detached
+    pub branch: Option<String>,
+    pub has_upstream: bool,
+    pub ahead: usize,
+    pub behind: usize,
+    pub is_dirty: bool,
+}
+
+fn current_branch(repo: &Repository) -> Option<Branch<'_>> {
+    repo.head()
+        .ok()
+        .filter(|head| head.is_branch())
+        .map(Branch::wrap)
+}
+
+fn count_ahead_behind(repo: &Repository, branch: &Branch) -> Option<(usize, usize)> {
+    let upstream = branch.upstream().ok()?;
+    let local_oid = branch.get().target()?;
+    let upstream_oid = upstream.get().target()?;
+    repo.graph_ahead_behind(local_oid, upstream_oid).ok()
+}
+
+/// Uncommitted changes, including untracked files but not ignored ones
+fn has_uncommitted_changes(repo: &Repository) -> bool {
+    let mut options = StatusOptions::new();
+    options.include_untracked(true).include_ignored(false);
+
+    repo.statuses(Some(&mut options))
+        .map(|statuses| !statuses.is_empty())
+        .unwrap_or(false)
+}
+
+/// Read branch, upstream distance and dirty state of an app that has its own `.git`
+pub fn read_git_status(app_path: &Path) -> Option<GitStatus> {
+    if !app_path.join(GIT_DIR).exists() {
+        return None;
+    }
+
+    let repo = Repository::open(app_path).ok()?;
+    let branch = current_branch(&repo);
+    let ahead_behind = branch
+        .as_ref()
+        .and_then(|branch| count_ahead_behind(&repo, branch));
+    let (ahead, behind) = ahead_behind.unwrap_or((0, 0));
+
+    Some(GitStatus {
+        branch: branch
+            .as_ref()
+            .and_then(|branch| branch.name().ok().flatten().map(str::to_string)),
+        has_upstream: ahead_behind.is_some(),
+        ahead,
+        behind,
+        is_dirty: has_uncommitted_changes(&repo),
+    })
+}
+
+/// Git status of one app, read on demand since walking a working tree is too slow to do
+/// for every app on each scan
+#[tauri::command]
+pub fn get_app_git_status(app_path: String) -> Result<Option<GitStatus>, String> {
+    Ok(read_git_status(Path::new(&app_path)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use git2::{BranchType, Signature};
+    use std::fs;
+
+    fn commit_all(repo: &Repository, message: &str) {
+        let mut index = repo.index().unwrap();
+        index
+            .add_all(["*"], git2::IndexAddOption::DEFAULT, None)
+            .unwrap();
+        index.write().unwrap();
+        let tree = repo.find_tree(index.write_tree().unwrap()).unwrap();
+        let signature = Signature::now("Test", "test@example.com").unwrap();
+        let parent = repo.head().ok().and_then(|head| head.peel_to_commit().ok());
+        let parents: Vec<_> = parent.iter().collect();
+
+        repo.commit(
+            Some("HEAD"),
+            &signature,
+            &signature,
+            message,
+            &tree,
+            &parents,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_read_git_status_without_repository() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(read_git_status(dir.path()), None);
+    }
+
+    #[test]
+    fn test_read_git_status_clean_and_dirty() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        fs::write(dir.path().join("App.mpr"), b"mpr").unwrap();
+        commit_all(&repo, "initial");
+
+        let status = read_git_status(dir.path()).unwrap();
+        assert!(status.branch.is_some());
+        assert!(!status.has_upstream);
+        assert!(!status.is_dirty);
+
+        fs::write(dir.path().join("App.mpr"), b"changed").unwrap();
+        assert!(read_git_status(dir.path()).unwrap().is_dirty);
+    }
+
+    #[test]
+    fn test_read_git_status_ahead_and_behind() {
+        let dir = tempfile::tempdir().unwrap();
+        let repo = Repository::init(dir.path()).unwrap();
+        fs::write(dir.path().join("App.mpr"), b"v1").unwrap();
+        commit_all(&repo, "initial");
+
+        let branch_name = read_git_status(dir.path()).unwrap().branch.unwrap();
+        let base = repo.head().unwrap().peel_to_commit().unwrap();
+        repo.remote("origin", "https://example.com/app.git")
+            .unwrap();
+        repo.reference("refs/remotes/origin/main", base.id(), true, "test")
+            .unwrap();
+        repo.find_branch(&branch_name, BranchType::Local)
+            .unwrap()
+            .set_upstream(Some("origin/main"))
+            .unwrap();
+
+        fs::write(dir.path().join("App.mpr"), b"v2").unwrap();
+        commit_all(&repo, "second");
+
+        let status = read_git_status(dir.path()).unwrap();
+        assert!(status.has_upstream);
+        assert_eq!((status.ahead, status.behind), (1, 0));
+        assert!(!status.is_dirty);
+    }
+}
@@ -1,13 +1,44 @@
+mod disk_usage;
 mod execution;
+mod git_status;
 mod models;
+mod mpr;
+mod orphans;
 mod paths;
+mod processes;
 mod scanner;
+mod trash;
+mod watcher;
+mod widget_inventory;
 
 // Re-export models
-pub use models::{MendixApp, MendixVersion};
+pub use disk_usage::{
+    AppCleanupResult, AppDiskUsage, CacheCategory, CategoryUsage, CleanupSummary,
+};
+pub use git_status::GitStatus;
+pub use models::{AppScanSettings, InstallResult, MendixApp, MendixVersion, OpenAppResult};
+pub use mpr::{get_mpr_metadata, MprMetadata};
+pub use orphans::{OrphanedApp, UnusedVersion, VersionUsageReport};
+pub use paths::InstallRoot;
+pub use processes::RunningStudioPro;
+pub use trash::TrashedApp;
+pub use watcher::{start_mendix_watcher, MendixChangeEvent};
+pub use widget_inventory::{AppWidgetInventory, PackagedWidget, WidgetPackage};
 
 // Re-export Tauri commands
 pub use execution::{
     delete_mendix_app, get_apps_by_version, get_installed_mendix_apps,
-    get_installed_mendix_versions, launch_studio_pro, uninstall_studio_pro_and_wait,
+    get_installed_mendix_versions, get_mendix_app_scan_settings, get_mendix_install_roots,
+    launch_studio_pro, list_trashed_apps, open_app_in_studio_pro, purge_trashed_apps,
+    restore_trashed_app, save_mendix_app_scan_settings, save_mendix_install_roots,
+    save_trash_retention_days, uninstall_studio_pro_and_wait,
 };
+pub use disk_usage::{cleanup_app_caches, get_apps_disk_usage};
+pub use git_status::get_app_git_status;
+pub use orphans::get_version_usage_report;
+pub use processes::get_running_studio_pro_instances;
+pub use widget_inventory::get_app_widget_inventory;
+
+pub(crate) use execution::wait_for_installed_version;
+pub(crate) use git_status::read_git_status;
+pub(crate) use widget_inventory::collect_app_widget_inventory;
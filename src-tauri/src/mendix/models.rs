@@ -1,13 +1,19 @@
 use chrono::{DateTime, Local};
 use serde::{Deserialize, Serialize};
 
+use super::git_status::GitStatus;
+use super::paths::InstallRoot;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MendixVersion {
     pub version: String,
+    pub full_version: String,
+    pub build_number: Option<String>,
     pub path: String,
     pub exe_path: String,
     pub install_date: Option<DateTime<Local>>,
     pub is_valid: bool,
+    pub install_root: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -15,8 +21,16 @@ pub struct MendixApp {
     pub name: String,
     pub path: String,
     pub version: Option<String>,
+    pub build_number: Option<String>,
     pub last_modified: Option<DateTime<Local>>,
     pub is_valid: bool,
+    pub mpr_path: Option<String>,
+    pub project_id: Option<String>,
+    #[serde(default)]
+    pub modules: Vec<String>,
+    /// Not read during scans; loaded on demand through `get_app_git_status`
+    #[serde(default)]
+    pub git_status: Option<GitStatus>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -25,3 +39,30 @@ pub struct UninstallResult {
     pub version: String,
     pub timed_out: bool,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstallResult {
+    pub success: bool,
+    pub version: String,
+    pub timed_out: bool,
+    pub exit_code: Option<i32>,
+    pub install_dir: Option<String>,
+    pub installed_version: Option<MendixVersion>,
+}
+
+/// Outcome of opening an app; when no exact install exists nothing is launched
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OpenAppResult {
+    pub launched: bool,
+    pub app_version: String,
+    pub matched_version: Option<MendixVersion>,
+    pub closest_newer_version: Option<MendixVersion>,
+    pub version_to_download: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppScanSettings {
+    pub roots: Vec<InstallRoot>,
+    pub max_depth: usize,
+    pub ignore_patterns: Vec<String>,
+}
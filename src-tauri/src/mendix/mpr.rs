@@ -0,0 +1,430 @@
+use regex::Regex;
+use rusqlite::{Connection, OpenFlags};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::SystemTime;
+
+/// Unit containment name used for modules in the `_Units` table
+const MODULES_CONTAINMENT_NAME: &str = "Modules";
+
+/// Folder holding unit contents for MPR v2 projects
+const MPR_CONTENTS_DIR: &str = "mprcontents";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MprMetadata {
+    pub product_version: String,
+    pub project_id: Option<String>,
+    pub modules: Vec<String>,
+}
+
+fn open_read_only(mpr_path: &Path) -> Result<Connection, String> {
+    if !mpr_path.is_file() {
+        return Err(format!("Project file not found: {}", mpr_path.display()));
+    }
+
+    Connection::open_with_flags(
+        mpr_path,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )
+    .map_err(|e| format!("Failed to open project file: {}", e))
+}
+
+/// Read the single `_MetaData` row as a column name -> text map
+fn read_metadata_row(conn: &Connection) -> Result<HashMap<String, String>, String> {
+    let mut stmt = conn
+        .prepare("SELECT * FROM _MetaData LIMIT 1")
+        .map_err(|e| format!("Failed to query project metadata: {}", e))?;
+
+    let column_names: Vec<String> = stmt
+        .column_names()
+        .into_iter()
+        .map(|name| name.to_string())
+        .collect();
+
+    stmt.query_row([], |row| {
+        Ok(column_names
+            .iter()
+            .enumerate()
+            .filter_map(|(index, name)| {
+                row.get::<_, Option<String>>(index)
+                    .ok()
+                    .flatten()
+                    .map(|value| (name.clone(), value))
+            })
+            .collect())
+    })
+    .map_err(|e| format!("Failed to read project metadata: {}", e))
+}
+
+/// Pick the most precise version from the metadata columns, preferring a four-part build
+fn extract_product_version(metadata: &HashMap<String, String>) -> Option<String> {
+    let version_regex = Regex::new(r"\d+\.\d+\.\d+(?:\.\d+)?").ok()?;
+
+    ["_BuildVersion", "_ProductVersion"]
+        .iter()
+        .filter_map(|column| metadata.get(*column))
+        .filter_map(|value| version_regex.find(value).map(|m| m.as_str().to_string()))
+        .max_by_key(|version| version.split('.').count())
+}
+
+fn has_column(conn: &Connection, table: &str, column: &str) -> bool {
+    conn.prepare(&format!("PRAGMA table_info({})", table))
+        .and_then(|mut stmt| {
+            stmt.query_map([], |row| row.get::<_, String>(1))
+                .map(|rows| rows.filter_map(|name| name.ok()).any(|name| name == column))
+        })
+        .unwrap_or(false)
+}
+
+/// Format a .NET GUID byte array (little-endian leading groups) as a string
+pub fn format_unit_id(bytes: &[u8]) -> Option<String> {
+    if bytes.len() != 16 {
+        return None;
+    }
+
+    let hex = |range: &[u8]| {
+        range
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect::<String>()
+    };
+    let reversed = |range: &[u8]| range.iter().rev().copied().collect::<Vec<u8>>();
+
+    Some(format!(
+        "{}-{}-{}-{}-{}",
+        hex(&reversed(&bytes[0..4])),
+        hex(&reversed(&bytes[4..6])),
+        hex(&reversed(&bytes[6..8])),
+        hex(&bytes[8..10]),
+        hex(&bytes[10..16])
+    ))
+}
+
+/// Location of a unit's contents file in an MPR v2 project
+fn unit_contents_path(project_dir: &Path, unit_id: &str) -> PathBuf {
+    project_dir
+        .join(MPR_CONTENTS_DIR)
+        .join(&unit_id[0..2])
+        .join(&unit_id[2..4])
+        .join(format!("{}.mxunit", unit_id))
+}
+
+fn read_module_contents_v1(conn: &Connection) -> Result<Vec<Vec<u8>>, String> {
+    let mut stmt = conn
+        .prepare("SELECT Contents FROM _Units WHERE ContainmentName = ?1")
+        .map_err(|e| format!("Failed to query project units: {}", e))?;
+
+    let rows = stmt
+        .query_map([MODULES_CONTAINMENT_NAME], |row| row.get::<_, Vec<u8>>(0))
+        .map_err(|e| format!("Failed to read project units: {}", e))?;
+
+    Ok(rows.filter_map(|contents| contents.ok()).collect())
+}
+
+fn read_module_contents_v2(conn: &Connection, project_dir: &Path) -> Result<Vec<Vec<u8>>, String> {
+    let mut stmt = conn
+        .prepare("SELECT UnitID FROM _Units WHERE ContainmentName = ?1")
+        .map_err(|e| format!("Failed to query project units: {}", e))?;
+
+    let rows = stmt
+        .query_map([MODULES_CONTAINMENT_NAME], |row| row.get::<_, Vec<u8>>(0))
+        .map_err(|e| format!("Failed to read project units: {}", e))?;
+
+    Ok(rows
+        .filter_map(|unit_id| unit_id.ok())
+        .filter_map(|unit_id| format_unit_id(&unit_id))
+        .filter_map(|unit_id| fs::read(unit_contents_path(project_dir, &unit_id)).ok())
+        .collect())
+}
+
+fn read_module_names(conn: &Connection, mpr_path: &Path) -> Result<Vec<String>, String> {
+    let contents = if has_column(conn, "_Units", "Contents") {
+        read_module_contents_v1(conn)?
+    } else {
+        let project_dir = mpr_path.parent().unwrap_or_else(|| Path::new("."));
+        read_module_contents_v2(conn, project_dir)?
+    };
+
+    let mut names: Vec<String> = contents
+        .iter()
+        .filter_map(|document| read_bson_string_field(document, "Name"))
+        .collect();
+    names.sort_by_key(|name| name.to_lowercase());
+    Ok(names)
+}
+
+/// Read a top-level string field from a BSON document
+pub fn read_bson_string_field(document: &[u8], field: &str) -> Option<String> {
+    let read_i32 = |offset: usize| -> Option<usize> {
+        document
+            .get(offset..offset + 4)
+            .map(|bytes| i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+            .and_then(|value| usize::try_from(value).ok())
+    };
+
+    let document_end = read_i32(0)?.min(document.len());
+    let mut offset = 4;
+
+    while offset < document_end {
+        let element_type = *document.get(offset)?;
+        if element_type == 0 {
+            return None;
+        }
+
+        let name_start = offset + 1;
+        let name_end = name_start + document[name_start..].iter().position(|b| *b == 0)?;
+        let name = std::str::from_utf8(&document[name_start..name_end]).ok()?;
+        let value_start = name_end + 1;
+
+        let value_len = match element_type {
+            0x01 | 0x09 | 0x11 | 0x12 => 8,
+            0x02 => 4 + read_i32(value_start)?,
+            0x03 | 0x04 => read_i32(value_start)?,
+            0x05 => 5 + read_i32(value_start)?,
+            0x07 => 12,
+            0x08 => 1,
+            0x0A => 0,
+            0x10 => 4,
+            0x13 => 16,
+            _ => return None,
+        };
+
+        if element_type == 0x02 && name == field {
+            let string_len = read_i32(value_start)?;
+            let bytes = document.get(value_start + 4..value_start + 4 + string_len)?;
+            let without_nul = bytes.strip_suffix(&[0]).unwrap_or(bytes);
+            return String::from_utf8(without_nul.to_vec()).ok();
+        }
+
+        offset = value_start + value_len;
+    }
+
+    None
+}
+
+/// Read product version, project id and modules from an `.mpr` project file
+pub fn read_mpr_metadata(mpr_path: &Path) -> Result<MprMetadata, String> {
+    let conn = open_read_only(mpr_path)?;
+    let metadata = read_metadata_row(&conn)?;
+
+    let product_version = extract_product_version(&metadata)
+        .ok_or_else(|| "Product version not found in project metadata".to_string())?;
+    let project_id = metadata
+        .get("_ProjectID")
+        .filter(|id| !id.trim().is_empty())
+        .cloned();
+    let modules = read_module_names(&conn, mpr_path).unwrap_or_default();
+
+    Ok(MprMetadata {
+        product_version,
+        project_id,
+        modules,
+    })
+}
+
+fn metadata_cache() -> &'static Mutex<HashMap<PathBuf, (SystemTime, MprMetadata)>> {
+    static CACHE: OnceLock<Mutex<HashMap<PathBuf, (SystemTime, MprMetadata)>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// `read_mpr_metadata`, reusing the previous result until the `.mpr` is modified so app
+/// scans do not open every project database again
+pub fn read_mpr_metadata_cached(mpr_path: &Path) -> Result<MprMetadata, String> {
+    let modified = fs::metadata(mpr_path)
+        .and_then(|metadata| metadata.modified())
+        .map_err(|e| format!("Failed to read project file: {}", e))?;
+
+    let cached = metadata_cache()
+        .lock()
+        .ok()
+        .and_then(|cache| cache.get(mpr_path).cloned());
+    if let Some((cached_at, metadata)) = cached {
+        if cached_at == modified {
+            return Ok(metadata);
+        }
+    }
+
+    let metadata = read_mpr_metadata(mpr_path)?;
+    if let Ok(mut cache) = metadata_cache().lock() {
+        cache.insert(mpr_path.to_path_buf(), (modified, metadata.clone()));
+    }
+    Ok(metadata)
+}
+
+#[tauri::command]
+pub fn get_mpr_metadata(mpr_path: String) -> Result<MprMetadata, String> {
+    read_mpr_metadata(Path::new(&mpr_path))
+}
+
+#[cfg(test)]
+pub(crate) mod test_support {
+    use rusqlite::Connection;
+    use std::path::Path;
+
+    /// Encode a flat BSON document containing only string fields
+    pub fn bson_with_strings(fields: &[(&str, &str)]) -> Vec<u8> {
+        let body: Vec<u8> = fields
+            .iter()
+            .flat_map(|(name, value)| {
+                let mut element = vec![0x02];
+                element.extend_from_slice(name.as_bytes());
+                element.push(0);
+                element.extend_from_slice(&((value.len() + 1) as i32).to_le_bytes());
+                element.extend_from_slice(value.as_bytes());
+                element.push(0);
+                element
+            })
+            .collect();
+
+        let total_len = (4 + body.len() + 1) as i32;
+        let mut document = total_len.to_le_bytes().to_vec();
+        document.extend(body);
+        document.push(0);
+        document
+    }
+
+    /// Create an MPR v1 style project file with metadata and module units
+    pub fn create_mpr_v1(path: &Path, build_version: &str, modules: &[&str]) {
+        let conn = Connection::open(path).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE _MetaData (_ProductVersion TEXT, _BuildVersion TEXT, _ProjectID TEXT);
+             CREATE TABLE _Units (UnitID BLOB, ContainerID BLOB, ContainmentName TEXT, Contents BLOB);",
+        )
+        .unwrap();
+        let product_version = build_version.rsplitn(2, '.').last().unwrap();
+        conn.execute(
+            "INSERT INTO _MetaData VALUES (?1, ?2, 'b6a9e3f0-1111-2222-3333-444455556666')",
+            [product_version, build_version],
+        )
+        .unwrap();
+
+        for (index, module) in modules.iter().enumerate() {
+            let contents = bson_with_strings(&[("$Type", "Projects$ModuleImpl"), ("Name", module)]);
+            conn.execute(
+                "INSERT INTO _Units VALUES (?1, NULL, 'Modules', ?2)",
+                rusqlite::params![vec![index as u8; 16], contents],
+            )
+            .unwrap();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::test_support::*;
+    use super::*;
+
+    #[test]
+    fn test_read_bson_string_field() {
+        let document = bson_with_strings(&[("$Type", "Projects$ModuleImpl"), ("Name", "Admin")]);
+
+        assert_eq!(
+            read_bson_string_field(&document, "Name"),
+            Some("Admin".to_string())
+        );
+        assert_eq!(read_bson_string_field(&document, "Missing"), None);
+        assert_eq!(read_bson_string_field(&[1, 2], "Name"), None);
+    }
+
+    #[test]
+    fn test_format_unit_id_uses_dotnet_byte_order() {
+        let bytes: Vec<u8> = (0u8..16).collect();
+        assert_eq!(
+            format_unit_id(&bytes),
+            Some("03020100-0504-0706-0809-0a0b0c0d0e0f".to_string())
+        );
+        assert_eq!(format_unit_id(&[0; 4]), None);
+    }
+
+    #[test]
+    fn test_read_mpr_metadata_v1() {
+        let dir = tempfile::tempdir().unwrap();
+        let mpr_path = dir.path().join("App.mpr");
+        create_mpr_v1(
+            &mpr_path,
+            "10.4.0.1234",
+            &["MyFirstModule", "Administration"],
+        );
+
+        let metadata = read_mpr_metadata(&mpr_path).unwrap();
+
+        assert_eq!(metadata.product_version, "10.4.0.1234");
+        assert_eq!(
+            metadata.project_id.as_deref(),
+            Some("b6a9e3f0-1111-2222-3333-444455556666")
+        );
+        assert_eq!(metadata.modules, vec!["Administration", "MyFirstModule"]);
+    }
+
+    #[test]
+    fn test_read_mpr_metadata_cached_until_modified() {
+        let dir = tempfile::tempdir().unwrap();
+        let mpr_path = dir.path().join("App.mpr");
+        create_mpr_v1(&mpr_path, "10.4.0.1234", &["MyFirstModule"]);
+        let modified = fs::metadata(&mpr_path).unwrap().modified().unwrap();
+
+        assert_eq!(
+            read_mpr_metadata_cached(&mpr_path).unwrap().product_version,
+            "10.4.0.1234"
+        );
+
+        let conn = Connection::open(&mpr_path).unwrap();
+        conn.execute("UPDATE _MetaData SET _BuildVersion = '10.5.0.2000'", [])
+            .unwrap();
+        drop(conn);
+        let file = fs::File::options().write(true).open(&mpr_path).unwrap();
+        file.set_modified(modified).unwrap();
+        assert_eq!(
+            read_mpr_metadata_cached(&mpr_path).unwrap().product_version,
+            "10.4.0.1234"
+        );
+
+        file.set_modified(modified + std::time::Duration::from_secs(5))
+            .unwrap();
+        assert_eq!(
+            read_mpr_metadata_cached(&mpr_path).unwrap().product_version,
+            "10.5.0.2000"
+        );
+    }
+
+    #[test]
+    fn test_read_mpr_metadata_v2_contents_folder() {
+        let dir = tempfile::tempdir().unwrap();
+        let mpr_path = dir.path().join("App.mpr");
+        let conn = Connection::open(&mpr_path).unwrap();
+        conn.execute_batch(
+            "CREATE TABLE _MetaData (_ProductVersion TEXT, _BuildVersion TEXT);
+             INSERT INTO _MetaData VALUES ('10.12.0', '10.12.0.5678');
+             CREATE TABLE _Units (UnitID BLOB, ContainerID BLOB, ContainmentName TEXT);",
+        )
+        .unwrap();
+        let unit_id: Vec<u8> = (0u8..16).collect();
+        conn.execute(
+            "INSERT INTO _Units VALUES (?1, NULL, 'Modules')",
+            [&unit_id],
+        )
+        .unwrap();
+
+        let unit_path = unit_contents_path(dir.path(), &format_unit_id(&unit_id).unwrap());
+        fs::create_dir_all(unit_path.parent().unwrap()).unwrap();
+        fs::write(&unit_path, bson_with_strings(&[("Name", "Sales")])).unwrap();
+
+        let metadata = read_mpr_metadata(&mpr_path).unwrap();
+
+        assert_eq!(metadata.product_version, "10.12.0.5678");
+        assert_eq!(metadata.project_id, None);
+        assert_eq!(metadata.modules, vec!["Sales"]);
+    }
+
+    #[test]
+    fn test_read_mpr_metadata_rejects_non_sqlite() {
+        let dir = tempfile::tempdir().unwrap();
+        let mpr_path = dir.path().join("Broken.mpr");
+        fs::write(&mpr_path, b"not a database").unwrap();
+
+        assert!(read_mpr_metadata(&mpr_path).is_err());
+    }
+}
@@ -0,0 +1,195 @@
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::storage::load_downloadable_versions_cache;
+use crate::web_scraper::DownloadableVersion;
+
+use super::disk_usage::calculate_directory_size;
+use super::execution::{get_installed_mendix_apps, get_installed_mendix_versions};
+use super::models::{MendixApp, MendixVersion};
+use super::scanner::{
+    find_installed_version, format_full_version, matches_version_query, split_version_and_build,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrphanedApp {
+    pub app: MendixApp,
+    pub required_version: String,
+    pub downloadable: Option<DownloadableVersion>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnusedVersion {
+    pub version: MendixVersion,
+    pub disk_size_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionUsageReport {
+    pub orphaned_apps: Vec<OrphanedApp>,
+    pub unused_versions: Vec<UnusedVersion>,
+}
+
+fn app_version_query(app: &MendixApp) -> Option<String> {
+    app.version
+        .as_deref()
+        .map(|version| format_full_version(version, app.build_number.as_deref()))
+}
+
+fn find_downloadable<'a>(
+    downloadable: &'a [DownloadableVersion],
+    version: &str,
+) -> Option<&'a DownloadableVersion> {
+    downloadable.iter().find(|candidate| {
+        split_version_and_build(candidate.version.trim())
+            .is_some_and(|(candidate_version, _)| candidate_version == version)
+    })
+}
+
+fn find_orphaned_apps(
+    apps: &[MendixApp],
+    versions: &[MendixVersion],
+    downloadable: &[DownloadableVersion],
+) -> Vec<OrphanedApp> {
+    apps.iter()
+        .filter_map(|app| app_version_query(app).map(|query| (app, query)))
+        .filter(|(_, query)| find_installed_version(versions, query).is_none())
+        .map(|(app, query)| OrphanedApp {
+            app: app.clone(),
+            downloadable: app
+                .version
+                .as_deref()
+                .and_then(|version| find_downloadable(downloadable, version))
+                .cloned(),
+            required_version: query,
+        })
+        .collect()
+}
+
+fn is_version_used(version: &MendixVersion, apps: &[MendixApp]) -> bool {
+    apps.iter().filter_map(app_version_query).any(|query| {
+        matches_version_query(&query, &version.version, version.build_number.as_deref())
+    })
+}
+
+/// Pair apps without a matching install with a download, and list installs no app needs
+pub fn build_version_usage_report(
+    apps: &[MendixApp],
+    versions: &[MendixVersion],
+    downloadable: &[DownloadableVersion],
+) -> VersionUsageReport {
+    let unused_versions = versions
+        .par_iter()
+        .filter(|version| !is_version_used(version, apps))
+        .map(|version| UnusedVersion {
+            version: version.clone(),
+            disk_size_bytes: calculate_directory_size(Path::new(&version.path)),
+        })
+        .collect();
+
+    VersionUsageReport {
+        orphaned_apps: find_orphaned_apps(apps, versions, downloadable),
+        unused_versions,
+    }
+}
+
+#[tauri::command]
+pub fn get_version_usage_report() -> Result<VersionUsageReport, String> {
+    let apps = get_installed_mendix_apps()?;
+    let versions = get_installed_mendix_versions()?;
+    let downloadable = load_downloadable_versions_cache().unwrap_or_default();
+
+    Ok(build_version_usage_report(&apps, &versions, &downloadable))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn installed(dir: &Path, version: &str, build: &str) -> MendixVersion {
+        let path = dir.join(format!("{}.{}", version, build));
+        fs::create_dir_all(&path).unwrap();
+        fs::write(path.join("payload.bin"), vec![0u8; 64]).unwrap();
+
+        MendixVersion {
+            version: version.to_string(),
+            full_version: format!("{}.{}", version, build),
+            build_number: Some(build.to_string()),
+            path: path.to_string_lossy().to_string(),
+            exe_path: String::new(),
+            install_date: None,
+            is_valid: true,
+            install_root: dir.to_string_lossy().to_string(),
+        }
+    }
+
+    fn app(name: &str, version: &str, build: Option<&str>) -> MendixApp {
+        MendixApp {
+            name: name.to_string(),
+            path: name.to_string(),
+            version: Some(version.to_string()),
+            build_number: build.map(str::to_string),
+            last_modified: None,
+            is_valid: true,
+            mpr_path: None,
+            project_id: None,
+            modules: Vec::new(),
+            git_status: None,
+        }
+    }
+
+    fn downloadable(version: &str) -> DownloadableVersion {
+        DownloadableVersion {
+            version: version.to_string(),
+            download_url: format!("https://example.com/{}", version),
+            release_date: None,
+            release_notes_url: None,
+            file_size: None,
+            is_lts: false,
+            is_beta: false,
+            is_mts: false,
+            is_latest: false,
+            first_seen_at: None,
+            build_number: None,
+            artifacts: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_build_version_usage_report() {
+        let root = tempfile::tempdir().unwrap();
+        let versions = vec![
+            installed(root.path(), "10.4.0", "1234"),
+            installed(root.path(), "9.24.0", "100"),
+        ];
+        let apps = vec![
+            app("Current", "10.4.0", Some("1234")),
+            app("NeedsDownload", "10.6.0", Some("555")),
+            app("WrongBuild", "10.4.0", Some("9999")),
+        ];
+        let cache = vec![downloadable("10.6.0"), downloadable("10.5.0")];
+
+        let report = build_version_usage_report(&apps, &versions, &cache);
+
+        let orphaned: Vec<_> = report
+            .orphaned_apps
+            .iter()
+            .map(|orphan| orphan.required_version.as_str())
+            .collect();
+        assert_eq!(orphaned, vec!["10.6.0.555", "10.4.0.9999"]);
+        assert_eq!(
+            report.orphaned_apps[0]
+                .downloadable
+                .as_ref()
+                .map(|d| d.version.as_str()),
+            Some("10.6.0")
+        );
+        assert!(report.orphaned_apps[1].downloadable.is_none());
+
+        assert_eq!(report.unused_versions.len(), 1);
+        assert_eq!(report.unused_versions[0].version.version, "9.24.0");
+        assert_eq!(report.unused_versions[0].disk_size_bytes, 64);
+    }
+}
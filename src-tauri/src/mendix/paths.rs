@@ -1,4 +1,8 @@
-use super::scanner::{extract_version_from_directory_name, scan_mendix_directory};
+use serde::{Deserialize, Serialize};
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+use super::scanner::{matches_version_query, scan_mendix_directory, split_version_and_build};
 
 /// Default Mendix installation directory
 pub const MENDIX_INSTALL_DIR: &str = "C:\\Program Files\\Mendix";
@@ -6,6 +10,128 @@ pub const MENDIX_INSTALL_DIR: &str = "C:\\Program Files\\Mendix";
 /// Default Mendix data directory (for uninstallers)
 pub const MENDIX_DATA_DIR: &str = "C:\\ProgramData\\Mendix";
 
+/// Environment variable holding extra install roots (OS path-list separated)
+pub const MENDIX_INSTALL_ROOTS_ENV: &str = "KIRAICHI_MENDIX_INSTALL_ROOTS";
+
+/// Environment variable holding extra app search roots (OS path-list separated)
+pub const MENDIX_APP_ROOTS_ENV: &str = "KIRAICHI_MENDIX_APP_ROOTS";
+
+/// Environment variable overriding the Mendix data directory
+pub const MENDIX_DATA_DIR_ENV: &str = "KIRAICHI_MENDIX_DATA_DIR";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InstallRootSource {
+    Environment,
+    Configured,
+    Default,
+}
+
+/// A directory that is scanned for Studio Pro installations or Mendix apps
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstallRoot {
+    pub path: String,
+    pub source: InstallRootSource,
+    pub exists: bool,
+}
+
+impl InstallRoot {
+    fn new(path: PathBuf, source: InstallRootSource) -> Self {
+        let exists = path.is_dir();
+        Self {
+            path: path.to_string_lossy().to_string(),
+            source,
+            exists,
+        }
+    }
+}
+
+/// Merge environment, configured and default roots (in that order), dropping duplicates
+fn resolve_scan_roots(
+    configured: &[String],
+    env_value: Option<OsString>,
+    default_root: Option<PathBuf>,
+) -> Vec<InstallRoot> {
+    let env_roots = env_value
+        .map(|value| std::env::split_paths(&value).collect::<Vec<_>>())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|path| (path, InstallRootSource::Environment));
+
+    let configured_roots = configured
+        .iter()
+        .map(|path| (PathBuf::from(path), InstallRootSource::Configured));
+
+    let default_roots = default_root
+        .into_iter()
+        .map(|path| (path, InstallRootSource::Default));
+
+    env_roots
+        .chain(configured_roots)
+        .chain(default_roots)
+        .filter(|(path, _)| !path.as_os_str().is_empty())
+        .fold(
+            Vec::<(PathBuf, InstallRootSource)>::new(),
+            |mut acc, (path, source)| {
+                if !acc
+                    .iter()
+                    .any(|(existing, _)| is_same_path(existing, &path))
+                {
+                    acc.push((path, source));
+                }
+                acc
+            },
+        )
+        .into_iter()
+        .map(|(path, source)| InstallRoot::new(path, source))
+        .collect()
+}
+
+/// Resolve the install roots from environment, configuration and defaults (in that order)
+pub fn resolve_install_roots(
+    configured: &[String],
+    env_value: Option<OsString>,
+) -> Vec<InstallRoot> {
+    resolve_scan_roots(
+        configured,
+        env_value,
+        Some(PathBuf::from(MENDIX_INSTALL_DIR)),
+    )
+}
+
+/// Resolve the install roots for the current process environment
+pub fn get_install_roots(configured: &[String]) -> Vec<InstallRoot> {
+    resolve_install_roots(configured, std::env::var_os(MENDIX_INSTALL_ROOTS_ENV))
+}
+
+/// Resolve the app search roots for the current process environment
+pub fn get_app_roots(configured: &[String]) -> Vec<InstallRoot> {
+    let default_root = get_mendix_apps_directory().ok().map(PathBuf::from);
+    resolve_scan_roots(
+        configured,
+        std::env::var_os(MENDIX_APP_ROOTS_ENV),
+        default_root,
+    )
+}
+
+/// Resolve the Mendix data directory, honouring the environment override
+pub fn get_mendix_data_directory() -> PathBuf {
+    std::env::var_os(MENDIX_DATA_DIR_ENV)
+        .filter(|value| !value.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(MENDIX_DATA_DIR))
+}
+
+pub(crate) fn is_same_path(a: &Path, b: &Path) -> bool {
+    let normalize = |path: &Path| {
+        path.to_string_lossy()
+            .trim_end_matches(['\\', '/'])
+            .replace('/', "\\")
+            .to_lowercase()
+    };
+    normalize(a) == normalize(b)
+}
+
 /// Get user home directory from environment
 pub fn get_home_directory() -> Result<String, String> {
     std::env::var("USERPROFILE")
@@ -16,27 +142,105 @@ pub fn get_home_directory() -> Result<String, String> {
 /// Get Mendix apps directory for current user
 pub fn get_mendix_apps_directory() -> Result<String, String> {
     let home_dir = get_home_directory()?;
-    Ok(format!("{}\\Mendix", home_dir))
+    Ok(Path::new(&home_dir)
+        .join("Mendix")
+        .to_string_lossy()
+        .to_string())
 }
 
-/// Construct the full exe path for a specific Mendix version
-pub fn construct_exe_path(mendix_dir: &str, version: &str) -> Option<String> {
-    let entries = scan_mendix_directory(mendix_dir).ok()?;
+/// Path of the Studio Pro executable inside an installation directory
+pub fn studio_pro_exe_path(install_dir: &Path) -> PathBuf {
+    install_dir.join("modeler").join("studiopro.exe")
+}
 
-    entries
+/// Path of the Inno Setup uninstaller inside a directory
+fn uninstaller_path(dir: &Path) -> PathBuf {
+    dir.join("unins000.exe")
+}
+
+/// Construct the uninstaller path for a specific Mendix version
+///
+/// The data directory is checked first; installs in custom locations keep
+/// their uninstaller next to the installation itself.
+pub fn construct_uninstall_path(
+    mendix_data_dir: &Path,
+    version: &str,
+    install_dir: Option<&Path>,
+) -> Option<PathBuf> {
+    let data_dir_candidate = scan_mendix_directory(&mendix_data_dir.to_string_lossy())
+        .ok()?
         .into_iter()
         .find(|(dir_name, _)| {
-            extract_version_from_directory_name(dir_name).is_some_and(|v| v == version)
+            split_version_and_build(dir_name).is_some_and(|(dir_version, dir_build)| {
+                matches_version_query(version, &dir_version, dir_build.as_deref())
+            })
         })
-        .map(|(_, path)| format!("{}\\modeler\\studiopro.exe", path))
-}
+        .map(|(_, path)| uninstaller_path(&Path::new(&path).join("uninst")));
 
-/// Construct the uninstaller path for a specific Mendix version
-pub fn construct_uninstall_path(mendix_data_dir: &str, version: &str) -> Option<String> {
-    let entries = scan_mendix_directory(mendix_data_dir).ok()?;
+    let install_dir_candidates = install_dir
+        .map(|dir| vec![uninstaller_path(&dir.join("uninst")), uninstaller_path(dir)])
+        .unwrap_or_default();
 
-    entries
+    data_dir_candidate
         .into_iter()
-        .find(|(dir_name, _)| dir_name.starts_with(version))
-        .map(|(_, path)| format!("{}\\uninst\\unins000.exe", path))
+        .chain(install_dir_candidates)
+        .find(|path| path.exists())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn join_paths(paths: &[&str]) -> OsString {
+        std::env::join_paths(paths).unwrap()
+    }
+
+    #[test]
+    fn test_resolve_install_roots_orders_sources() {
+        let roots = resolve_install_roots(
+            &["D:\\Mendix".to_string()],
+            Some(join_paths(&["PortableRoot"])),
+        );
+
+        let sources: Vec<_> = roots.iter().map(|root| root.source).collect();
+        assert_eq!(
+            sources,
+            vec![
+                InstallRootSource::Environment,
+                InstallRootSource::Configured,
+                InstallRootSource::Default,
+            ]
+        );
+        assert_eq!(roots[0].path, "PortableRoot");
+        assert_eq!(roots[2].path, MENDIX_INSTALL_DIR);
+    }
+
+    #[test]
+    fn test_resolve_install_roots_dedupes_default() {
+        let roots = resolve_install_roots(&["c:\\program files\\mendix\\".to_string()], None);
+
+        assert_eq!(roots.len(), 1);
+        assert_eq!(roots[0].source, InstallRootSource::Configured);
+    }
+
+    #[test]
+    fn test_studio_pro_exe_path() {
+        let expected = Path::new("root").join("modeler").join("studiopro.exe");
+        assert_eq!(studio_pro_exe_path(Path::new("root")), expected);
+    }
+
+    #[test]
+    fn test_construct_uninstall_path_matches_whole_build_number() {
+        let data_dir = tempfile::tempdir().unwrap();
+        for build in ["10.4.0.1234", "10.4.0.123"] {
+            let uninst = data_dir.path().join(build).join("uninst");
+            std::fs::create_dir_all(&uninst).unwrap();
+            std::fs::write(uninstaller_path(&uninst), "").unwrap();
+        }
+
+        let path = construct_uninstall_path(data_dir.path(), "10.4.0.123", None);
+
+        let expected = uninstaller_path(&data_dir.path().join("10.4.0.123").join("uninst"));
+        assert_eq!(path, Some(expected));
+    }
 }
@@ -0,0 +1,288 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
+
+use super::paths::is_same_path;
+
+/// Executable name of Studio Pro, compared case-insensitively
+const STUDIO_PRO_EXE_NAME: &str = "studiopro.exe";
+
+/// Minimal view of an OS process needed to recognise Studio Pro
+#[derive(Debug, Clone)]
+pub struct ProcessInfo {
+    pub pid: u32,
+    pub name: String,
+    pub exe_path: Option<PathBuf>,
+    pub args: Vec<String>,
+}
+
+/// Source of running processes, replaceable in tests
+pub trait ProcessLister {
+    fn list_processes(&self) -> Vec<ProcessInfo>;
+}
+
+/// Lists processes of the current machine through sysinfo
+pub struct SystemProcessLister;
+
+impl ProcessLister for SystemProcessLister {
+    fn list_processes(&self) -> Vec<ProcessInfo> {
+        let mut system = System::new();
+        system.refresh_processes_specifics(
+            ProcessesToUpdate::All,
+            true,
+            ProcessRefreshKind::nothing()
+                .with_exe(UpdateKind::OnlyIfNotSet)
+                .with_cmd(UpdateKind::OnlyIfNotSet),
+        );
+
+        system
+            .processes()
+            .values()
+            .map(|process| ProcessInfo {
+                pid: process.pid().as_u32(),
+                name: process.name().to_string_lossy().to_string(),
+                exe_path: process.exe().map(Path::to_path_buf),
+                args: process
+                    .cmd()
+                    .iter()
+                    .map(|arg| arg.to_string_lossy().to_string())
+                    .collect(),
+            })
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RunningStudioPro {
+    pub pid: u32,
+    pub exe_path: Option<String>,
+    pub install_dir: Option<String>,
+    pub project_path: Option<String>,
+}
+
+fn is_studio_pro_process(process: &ProcessInfo) -> bool {
+    let exe_name = process
+        .exe_path
+        .as_ref()
+        .and_then(|path| path.file_name())
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| process.name.clone());
+
+    exe_name.eq_ignore_ascii_case(STUDIO_PRO_EXE_NAME)
+}
+
+/// `<install>/modeler/studiopro.exe` -> `<install>`
+fn install_dir_from_exe(exe_path: &Path) -> Option<PathBuf> {
+    exe_path
+        .parent()
+        .and_then(Path::parent)
+        .map(Path::to_path_buf)
+}
+
+fn project_from_args(args: &[String]) -> Option<String> {
+    args.iter()
+        .skip(1)
+        .map(|arg| arg.trim_matches('"'))
+        .find(|arg| arg.to_lowercase().ends_with(".mpr"))
+        .map(str::to_string)
+}
+
+/// Map running Studio Pro processes to their install directories and open projects
+pub fn find_running_studio_pro(lister: &dyn ProcessLister) -> Vec<RunningStudioPro> {
+    lister
+        .list_processes()
+        .into_iter()
+        .filter(is_studio_pro_process)
+        .map(|process| RunningStudioPro {
+            pid: process.pid,
+            install_dir: process
+                .exe_path
+                .as_deref()
+                .and_then(install_dir_from_exe)
+                .map(|dir| dir.to_string_lossy().to_string()),
+            exe_path: process
+                .exe_path
+                .as_ref()
+                .map(|path| path.to_string_lossy().to_string()),
+            project_path: project_from_args(&process.args),
+        })
+        .collect()
+}
+
+/// Fail when a Studio Pro process is running from the given installation
+pub fn ensure_install_not_in_use(
+    running: &[RunningStudioPro],
+    install_dir: &Path,
+) -> Result<(), String> {
+    match running.iter().find(|instance| {
+        instance
+            .install_dir
+            .as_ref()
+            .is_some_and(|dir| is_same_path(Path::new(dir), install_dir))
+    }) {
+        Some(instance) => Err(format!(
+            "Studio Pro from {} is still running (pid {}). Close it first.",
+            install_dir.display(),
+            instance.pid
+        )),
+        None => Ok(()),
+    }
+}
+
+/// Whether another process holds the file open. Studio Pro keeps the `.mpr` open while a
+/// project is loaded, which Windows reports as a sharing violation on an exclusive open.
+#[cfg(target_os = "windows")]
+fn is_file_locked(path: &Path) -> bool {
+    use std::os::windows::fs::OpenOptionsExt;
+    const ERROR_SHARING_VIOLATION: i32 = 32;
+    const ERROR_LOCK_VIOLATION: i32 = 33;
+
+    match fs::OpenOptions::new().read(true).share_mode(0).open(path) {
+        Ok(_) => false,
+        Err(e) => matches!(
+            e.raw_os_error(),
+            Some(ERROR_SHARING_VIOLATION) | Some(ERROR_LOCK_VIOLATION)
+        ),
+    }
+}
+
+/// Other platforms have no mandatory file locks, so only the process check applies
+#[cfg(not(target_os = "windows"))]
+fn is_file_locked(_path: &Path) -> bool {
+    false
+}
+
+/// `.mpr` files of the app that another process holds open
+fn find_locked_project_files(app_path: &Path) -> Vec<PathBuf> {
+    fs::read_dir(app_path)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    path.extension()
+                        .is_some_and(|extension| extension.eq_ignore_ascii_case("mpr"))
+                })
+                .filter(|path| is_file_locked(path))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Fail when a Studio Pro process has a project of the given app open. Projects opened
+/// from within Studio Pro do not show up in its command line, so the `.mpr` files are
+/// also checked for being held open.
+pub fn ensure_app_not_in_use(running: &[RunningStudioPro], app_path: &Path) -> Result<(), String> {
+    if let Some(instance) = running.iter().find(|instance| {
+        instance
+            .project_path
+            .as_ref()
+            .and_then(|project| Path::new(project).parent())
+            .is_some_and(|project_dir| is_same_path(project_dir, app_path))
+    }) {
+        return Err(format!(
+            "{} is open in Studio Pro (pid {}). Close it first.",
+            app_path.display(),
+            instance.pid
+        ));
+    }
+
+    match find_locked_project_files(app_path).first() {
+        Some(project) => Err(format!(
+            "{} is open in another program. Close it first.",
+            project.display()
+        )),
+        None => Ok(()),
+    }
+}
+
+#[tauri::command]
+pub fn get_running_studio_pro_instances() -> Result<Vec<RunningStudioPro>, String> {
+    Ok(find_running_studio_pro(&SystemProcessLister))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct FakeProcessLister(Vec<ProcessInfo>);
+
+    impl ProcessLister for FakeProcessLister {
+        fn list_processes(&self) -> Vec<ProcessInfo> {
+            self.0.clone()
+        }
+    }
+
+    fn process(pid: u32, exe_path: &Path, args: &[&str]) -> ProcessInfo {
+        ProcessInfo {
+            pid,
+            name: exe_path.file_name().unwrap().to_string_lossy().to_string(),
+            exe_path: Some(exe_path.to_path_buf()),
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+        }
+    }
+
+    fn fake_lister() -> FakeProcessLister {
+        let install = Path::new("Mendix").join("10.4.0.1234");
+        let exe = install.join("modeler").join("StudioPro.exe");
+        let project = Path::new("apps").join("MyApp").join("MyApp.mpr");
+
+        FakeProcessLister(vec![
+            process(
+                10,
+                &exe,
+                &[&exe.to_string_lossy(), &project.to_string_lossy()],
+            ),
+            process(11, Path::new("notepad.exe"), &["notepad.exe"]),
+        ])
+    }
+
+    #[test]
+    fn test_find_running_studio_pro() {
+        let running = find_running_studio_pro(&fake_lister());
+
+        assert_eq!(running.len(), 1);
+        assert_eq!(running[0].pid, 10);
+        assert_eq!(
+            running[0].install_dir,
+            Some(
+                Path::new("Mendix")
+                    .join("10.4.0.1234")
+                    .to_string_lossy()
+                    .to_string()
+            )
+        );
+        assert!(running[0]
+            .project_path
+            .as_deref()
+            .is_some_and(|path| path.ends_with("MyApp.mpr")));
+    }
+
+    #[test]
+    fn test_ensure_install_not_in_use() {
+        let running = find_running_studio_pro(&fake_lister());
+
+        assert!(
+            ensure_install_not_in_use(&running, &Path::new("Mendix").join("10.4.0.1234")).is_err()
+        );
+        assert!(ensure_install_not_in_use(&running, &Path::new("Mendix").join("9.24.0.1")).is_ok());
+    }
+
+    #[test]
+    fn test_ensure_app_not_in_use() {
+        let running = find_running_studio_pro(&fake_lister());
+
+        assert!(ensure_app_not_in_use(&running, &Path::new("apps").join("MyApp")).is_err());
+        assert!(ensure_app_not_in_use(&running, &Path::new("apps").join("Other")).is_ok());
+    }
+
+    #[test]
+    fn test_closed_project_files_are_not_locked() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("MyApp.mpr"), b"").unwrap();
+
+        assert!(find_locked_project_files(dir.path()).is_empty());
+        assert!(ensure_app_not_in_use(&[], dir.path()).is_ok());
+    }
+}
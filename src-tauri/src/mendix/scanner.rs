@@ -1,10 +1,43 @@
 use chrono::{DateTime, Local};
-use regex::Regex;
+use regex::{Regex, RegexSet};
+use std::collections::HashSet;
 use std::fs;
 use std::path::Path;
 use walkdir::WalkDir;
 
 use super::models::{MendixApp, MendixVersion};
+use super::mpr::read_mpr_metadata_cached;
+use super::paths::{studio_pro_exe_path, InstallRoot};
+use super::trash::APP_TRASH_DIR;
+
+/// Per-user settings file Studio Pro writes next to the project
+pub const PROJECT_SETTINGS_FILE: &str = "project-settings.user.json";
+
+/// Options controlling the recursive app search
+#[derive(Debug, Clone)]
+pub struct AppScanOptions {
+    pub max_depth: usize,
+    pub ignore_patterns: IgnorePatterns,
+}
+
+/// Directory name patterns (`*` wildcards, case-insensitive) compiled once, so a scan does
+/// not build a regex for every directory it visits
+#[derive(Debug, Clone)]
+pub struct IgnorePatterns(RegexSet);
+
+impl IgnorePatterns {
+    pub fn new(patterns: &[String]) -> Self {
+        let sources = patterns
+            .iter()
+            .map(|pattern| format!("(?i)^{}$", regex::escape(pattern).replace("\\*", ".*")));
+
+        IgnorePatterns(RegexSet::new(sources).unwrap_or_else(|_| RegexSet::empty()))
+    }
+
+    pub fn is_match(&self, dir_name: &str) -> bool {
+        self.0.is_match(dir_name)
+    }
+}
 
 /// Extract installation date from directory metadata
 pub fn extract_install_date(path: &str) -> Option<DateTime<Local>> {
@@ -46,7 +79,7 @@ pub fn extract_version_from_project_settings(file_path: &str) -> Option<String>
         .filter_map(|part| part.get("type")?.as_str())
         .find(|type_str| type_str.contains("Version="))
         .and_then(|type_str| {
-            Regex::new(r"Version=(\d+\.\d+\.\d+)")
+            Regex::new(r"Version=(\d+\.\d+\.\d+(?:\.\d+)?)")
                 .ok()?
                 .captures(type_str)?
                 .get(1)
@@ -54,13 +87,45 @@ pub fn extract_version_from_project_settings(file_path: &str) -> Option<String>
         })
 }
 
-/// Extract version number from directory name (e.g., "10.4.0.1234" -> "10.4.0")
-pub fn extract_version_from_directory_name(dir_name: &str) -> Option<String> {
-    Regex::new(r"^(\d+\.\d+\.\d+)")
+/// Split a version into its three-part version and build number
+/// (e.g., "10.4.0.1234" -> ("10.4.0", Some("1234")))
+pub fn split_version_and_build(value: &str) -> Option<(String, Option<String>)> {
+    let captures = Regex::new(r"^(\d+\.\d+\.\d+)(?:\.(\d+))?")
         .ok()?
-        .captures(dir_name)?
-        .get(1)
-        .map(|m| m.as_str().to_string())
+        .captures(value)?;
+
+    let version = captures.get(1)?.as_str().to_string();
+    let build_number = captures.get(2).map(|m| m.as_str().to_string());
+    Some((version, build_number))
+}
+
+/// Join a three-part version and optional build number back into one string
+pub fn format_full_version(version: &str, build_number: Option<&str>) -> String {
+    match build_number {
+        Some(build) => format!("{}.{}", version, build),
+        None => version.to_string(),
+    }
+}
+
+/// Check whether a version/build pair matches a version query
+///
+/// Queries carrying a build number ("10.4.0.1234") only match a candidate with that
+/// build, so an install of unknown build never stands in for a specific one; three-part
+/// queries ("10.4.0") match any build.
+pub fn matches_version_query(query: &str, version: &str, build_number: Option<&str>) -> bool {
+    match split_version_and_build(query) {
+        Some((query_version, Some(query_build))) => {
+            query_version == version && build_number == Some(query_build.as_str())
+        }
+        Some((query_version, None)) => query_version == version,
+        None => false,
+    }
+}
+
+fn parse_build_number(build_number: Option<&str>) -> u64 {
+    build_number
+        .and_then(|build| build.parse().ok())
+        .unwrap_or(0)
 }
 
 /// Scan a directory and return list of (name, path) tuples for subdirectories
@@ -84,52 +149,208 @@ pub fn scan_mendix_directory(dir_path: &str) -> Result<Vec<(String, String)>, St
         .collect()
 }
 
-/// Create a MendixVersion from version string and path
-pub fn create_mendix_version(version: String, path: String) -> MendixVersion {
-    let exe_path = format!("{}\\modeler\\studiopro.exe", path);
-    let is_valid = Path::new(&exe_path).exists();
+/// Create a MendixVersion from version, build number, path and the root it was found in
+pub fn create_mendix_version(
+    version: String,
+    build_number: Option<String>,
+    path: String,
+    install_root: String,
+) -> MendixVersion {
+    let exe_path = studio_pro_exe_path(Path::new(&path));
+    let is_valid = exe_path.exists();
     let install_date = extract_install_date(&path);
+    let full_version = format_full_version(&version, build_number.as_deref());
 
     MendixVersion {
         version,
+        full_version,
+        build_number,
         path,
-        exe_path,
+        exe_path: exe_path.to_string_lossy().to_string(),
         install_date,
         is_valid,
+        install_root,
     }
 }
 
+/// Find the `.mpr` project file directly inside a directory
+pub fn find_mpr_file(dir: &Path) -> Option<std::path::PathBuf> {
+    fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .find(|path| {
+            path.extension()
+                .and_then(|ext| ext.to_str())
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("mpr"))
+        })
+}
+
+/// Check whether a directory looks like a Mendix app (mpr or project settings present)
+pub fn is_mendix_app_directory(dir: &Path) -> bool {
+    find_mpr_file(dir).is_some() || dir.join(PROJECT_SETTINGS_FILE).exists()
+}
+
 /// Create a MendixApp from name and path
 pub fn create_mendix_app(name: String, path: String) -> MendixApp {
-    let project_settings_path = format!("{}\\project-settings.user.json", path);
-    let is_valid = Path::new(&project_settings_path).exists();
+    let app_dir = Path::new(&path);
+    let project_settings_path = app_dir.join(PROJECT_SETTINGS_FILE);
+    let mpr_path = find_mpr_file(app_dir).map(|mpr| mpr.to_string_lossy().to_string());
+    let has_project_settings = project_settings_path.exists();
+    let is_valid = has_project_settings || mpr_path.is_some();
     let last_modified = extract_last_modified(&path);
-    let version = if is_valid {
-        extract_version_from_project_settings(&project_settings_path)
-    } else {
-        None
+    let mpr_metadata = mpr_path
+        .as_ref()
+        .and_then(|mpr| read_mpr_metadata_cached(Path::new(mpr)).ok());
+
+    let full_version = match &mpr_metadata {
+        Some(metadata) => Some(metadata.product_version.clone()),
+        None if has_project_settings => {
+            extract_version_from_project_settings(&project_settings_path.to_string_lossy())
+        }
+        None => None,
     };
 
+    let (version, build_number) = full_version
+        .and_then(|full_version| split_version_and_build(&full_version))
+        .map(|(version, build)| (Some(version), build.filter(|b| b != "0")))
+        .unwrap_or((None, None));
+
+    let (project_id, modules) = mpr_metadata
+        .map(|metadata| (metadata.project_id, metadata.modules))
+        .unwrap_or_default();
     MendixApp {
         name,
         path,
         version,
+        build_number,
         last_modified,
         is_valid,
+        mpr_path,
+        project_id,
+        modules,
+        git_status: None,
     }
 }
 
 /// Process directory entries into MendixVersions
-pub fn process_mendix_versions(directory_entries: Vec<(String, String)>) -> Vec<MendixVersion> {
+pub fn process_mendix_versions(
+    directory_entries: Vec<(String, String)>,
+    install_root: &str,
+) -> Vec<MendixVersion> {
     directory_entries
         .into_iter()
         .filter_map(|(dir_name, path)| {
-            extract_version_from_directory_name(&dir_name)
-                .map(|version| create_mendix_version(version, path))
+            split_version_and_build(&dir_name).map(|(version, build_number)| {
+                create_mendix_version(version, build_number, path, install_root.to_string())
+            })
         })
         .collect()
 }
 
+/// Directory entry for a root that is itself a (portable) Studio Pro installation
+fn portable_root_entry(root_path: &Path) -> Option<(String, String)> {
+    if !studio_pro_exe_path(root_path).exists() {
+        return None;
+    }
+
+    root_path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .map(|name| (name.to_string(), root_path.to_string_lossy().to_string()))
+}
+
+/// Scan a single install root for Studio Pro installations
+pub fn scan_install_root(root: &InstallRoot) -> Vec<MendixVersion> {
+    let root_path = Path::new(&root.path);
+    let entries = portable_root_entry(root_path)
+        .into_iter()
+        .chain(scan_mendix_directory(&root.path).unwrap_or_default())
+        .collect();
+
+    process_mendix_versions(entries, &root.path)
+}
+
+/// Installation in a directory chosen at install time, which may sit outside every install
+/// root. A folder not named after a version, or named without a build, is taken to hold
+/// the queried version.
+pub fn scan_install_directory(install_dir: &Path, version_query: &str) -> Option<MendixVersion> {
+    if !studio_pro_exe_path(install_dir).exists() {
+        return None;
+    }
+
+    let (query_version, query_build) = split_version_and_build(version_query)?;
+    let dir_name = install_dir.file_name().and_then(|name| name.to_str());
+    let (version, build_number) = match dir_name.and_then(split_version_and_build) {
+        Some((version, None)) if version == query_version => (version, query_build),
+        Some((version, build_number))
+            if matches_version_query(version_query, &version, build_number.as_deref()) =>
+        {
+            (version, build_number)
+        }
+        Some(_) => return None,
+        None => (query_version, query_build),
+    };
+    let install_root = install_dir
+        .parent()
+        .map(|parent| parent.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    Some(create_mendix_version(
+        version,
+        build_number,
+        install_dir.to_string_lossy().to_string(),
+        install_root,
+    ))
+}
+
+/// Scan every install root, keeping the first occurrence of each installation path
+pub fn scan_install_roots(roots: &[InstallRoot]) -> Vec<MendixVersion> {
+    let mut seen_paths = HashSet::new();
+
+    roots
+        .iter()
+        .filter(|root| root.exists)
+        .flat_map(scan_install_root)
+        .filter(|version| seen_paths.insert(version.path.to_lowercase()))
+        .collect()
+}
+
+/// Find the installation matching a version query, preferring the newest build
+pub fn find_installed_version<'a>(
+    versions: &'a [MendixVersion],
+    query: &str,
+) -> Option<&'a MendixVersion> {
+    versions
+        .iter()
+        .filter(|installed| {
+            matches_version_query(query, &installed.version, installed.build_number.as_deref())
+        })
+        .max_by_key(|installed| parse_build_number(installed.build_number.as_deref()))
+}
+
+/// Split a dotted version into numeric parts for ordering
+fn parse_version_parts(version: &str) -> Vec<u32> {
+    version.split('.').map(|s| s.parse().unwrap_or(0)).collect()
+}
+
+/// Find the oldest installation of the same major version that is newer than the query
+pub fn find_closest_newer_version<'a>(
+    versions: &'a [MendixVersion],
+    query: &str,
+) -> Option<&'a MendixVersion> {
+    let query_parts = parse_version_parts(query);
+    let query_major = query_parts.first().copied();
+
+    versions
+        .iter()
+        .map(|installed| (installed, parse_version_parts(&installed.full_version)))
+        .filter(|(_, parts)| parts.first().copied() == query_major && *parts > query_parts)
+        .min_by(|(_, a), (_, b)| a.cmp(b))
+        .map(|(installed, _)| installed)
+}
+
 /// Process directory entries into MendixApps
 pub fn process_mendix_apps(directory_entries: Vec<(String, String)>) -> Vec<MendixApp> {
     directory_entries
@@ -138,17 +359,55 @@ pub fn process_mendix_apps(directory_entries: Vec<(String, String)>) -> Vec<Mend
         .collect()
 }
 
+/// Recursively search a root for app directories, without descending into found apps
+pub fn find_app_directories(root: &Path, options: &AppScanOptions) -> Vec<(String, String)> {
+    let mut found = Vec::new();
+    let mut walker = WalkDir::new(root).max_depth(options.max_depth).into_iter();
+
+    while let Some(entry) = walker.next() {
+        let Ok(entry) = entry else { continue };
+
+        if !entry.file_type().is_dir() {
+            continue;
+        }
+
+        let dir_name = entry.file_name().to_string_lossy().to_string();
+
+        if entry.depth() > 0
+            && (dir_name == APP_TRASH_DIR || options.ignore_patterns.is_match(&dir_name))
+        {
+            walker.skip_current_dir();
+            continue;
+        }
+
+        if is_mendix_app_directory(entry.path()) {
+            found.push((dir_name, entry.path().to_string_lossy().to_string()));
+            walker.skip_current_dir();
+        }
+    }
+
+    found
+}
+
+/// Search every app root, keeping the first occurrence of each app path
+pub fn scan_app_roots(roots: &[InstallRoot], options: &AppScanOptions) -> Vec<MendixApp> {
+    let mut seen_paths = HashSet::new();
+
+    let entries = roots
+        .iter()
+        .filter(|root| root.exists)
+        .flat_map(|root| find_app_directories(Path::new(&root.path), options))
+        .filter(|(_, path)| seen_paths.insert(path.to_lowercase()))
+        .collect();
+
+    process_mendix_apps(entries)
+}
+
 /// Sort versions in descending order (newest first)
 pub fn sort_versions_by_descending(versions: Vec<MendixVersion>) -> Vec<MendixVersion> {
     let mut sorted = versions;
     sorted.sort_by(|a, b| {
-        let parse_version = |version: &str| -> Vec<u32> {
-            version.split('.').map(|s| s.parse().unwrap_or(0)).collect()
-        };
-
-        let a_parts = parse_version(&a.version);
-        let b_parts = parse_version(&b.version);
-        b_parts.cmp(&a_parts)
+        parse_version_parts(&b.full_version).cmp(&parse_version_parts(&a.full_version))
     });
     sorted
 }
@@ -170,14 +429,306 @@ pub fn filter_valid_versions(versions: Vec<MendixVersion>) -> Vec<MendixVersion>
     versions.into_iter().filter(|v| v.is_valid).collect()
 }
 
-/// Filter to only valid apps (with existing project settings)
+/// Filter to only valid apps (with mpr or project settings)
 pub fn filter_valid_apps(apps: Vec<MendixApp>) -> Vec<MendixApp> {
     apps.into_iter().filter(|app| app.is_valid).collect()
 }
 
-/// Filter apps by specific version
+/// Filter apps by version query (exact build when the query carries one)
 pub fn filter_apps_by_version(apps: Vec<MendixApp>, target_version: &str) -> Vec<MendixApp> {
     apps.into_iter()
-        .filter(|app| app.version.as_ref() == Some(&target_version.to_string()))
+        .filter(|app| {
+            app.version.as_deref().is_some_and(|version| {
+                matches_version_query(target_version, version, app.build_number.as_deref())
+            })
+        })
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mendix::paths::InstallRootSource;
+    use std::fs;
+
+    fn create_fake_install(dir: &Path) {
+        let modeler_dir = dir.join("modeler");
+        fs::create_dir_all(&modeler_dir).unwrap();
+        fs::write(modeler_dir.join("studiopro.exe"), b"").unwrap();
+    }
+
+    fn scan_root(path: &Path) -> InstallRoot {
+        InstallRoot {
+            path: path.to_string_lossy().to_string(),
+            source: InstallRootSource::Configured,
+            exists: path.is_dir(),
+        }
+    }
+
+    #[test]
+    fn test_split_version_and_build() {
+        assert_eq!(
+            split_version_and_build("10.4.0.1234"),
+            Some(("10.4.0".to_string(), Some("1234".to_string())))
+        );
+        assert_eq!(
+            split_version_and_build("10.4.0"),
+            Some(("10.4.0".to_string(), None))
+        );
+        assert_eq!(split_version_and_build("latest"), None);
+    }
+
+    #[test]
+    fn test_matches_version_query() {
+        assert!(matches_version_query("10.4.0", "10.4.0", Some("1234")));
+        assert!(matches_version_query("10.4.0.1234", "10.4.0", Some("1234")));
+        assert!(!matches_version_query(
+            "10.4.0.1234",
+            "10.4.0",
+            Some("5678")
+        ));
+        assert!(!matches_version_query("10.4.0.1234", "10.4.0", None));
+        assert!(matches_version_query("10.4.0", "10.4.0", None));
+        assert!(!matches_version_query("10.4.1", "10.4.0", Some("1234")));
+    }
+
+    #[test]
+    fn test_scan_keeps_hotfix_builds_apart() {
+        let root = tempfile::tempdir().unwrap();
+        create_fake_install(&root.path().join("10.4.0.1234"));
+        create_fake_install(&root.path().join("10.4.0.5678"));
+
+        let versions = sort_versions_by_descending(scan_install_roots(&[scan_root(root.path())]));
+
+        assert_eq!(versions.len(), 2);
+        assert_eq!(versions[0].full_version, "10.4.0.5678");
+        assert_eq!(versions[1].full_version, "10.4.0.1234");
+
+        let exact = find_installed_version(&versions, "10.4.0.1234").unwrap();
+        assert_eq!(exact.build_number.as_deref(), Some("1234"));
+        let newest = find_installed_version(&versions, "10.4.0").unwrap();
+        assert_eq!(newest.build_number.as_deref(), Some("5678"));
+        assert!(find_installed_version(&versions, "10.4.0.9999").is_none());
+    }
+
+    #[test]
+    fn test_find_closest_newer_version() {
+        let root = tempfile::tempdir().unwrap();
+        for version in ["9.24.10.100", "10.4.0.1234", "10.6.1.200", "11.0.0.1"] {
+            create_fake_install(&root.path().join(version));
+        }
+        let versions = scan_install_roots(&[scan_root(root.path())]);
+
+        let closest = find_closest_newer_version(&versions, "10.4.0.9999").unwrap();
+        assert_eq!(closest.full_version, "10.6.1.200");
+        assert!(find_closest_newer_version(&versions, "9.24.10.100").is_none());
+        assert!(find_closest_newer_version(&versions, "11.0.0.1").is_none());
+    }
+
+    #[test]
+    fn test_filter_apps_by_version_with_build() {
+        let app = |name: &str, build: Option<&str>| MendixApp {
+            name: name.to_string(),
+            path: name.to_string(),
+            version: Some("10.4.0".to_string()),
+            build_number: build.map(|b| b.to_string()),
+            last_modified: None,
+            is_valid: true,
+            mpr_path: None,
+            project_id: None,
+            modules: Vec::new(),
+            git_status: None,
+        };
+        let apps = vec![
+            app("Exact", Some("1234")),
+            app("Other", Some("5678")),
+            app("Unknown", None),
+        ];
+
+        let by_build: Vec<_> = filter_apps_by_version(apps.clone(), "10.4.0.1234")
+            .into_iter()
+            .map(|a| a.name)
+            .collect();
+        assert_eq!(by_build, vec!["Exact"]);
+        assert_eq!(filter_apps_by_version(apps, "10.4.0").len(), 3);
+    }
+
+    fn app_scan_options() -> AppScanOptions {
+        AppScanOptions {
+            max_depth: 4,
+            ignore_patterns: IgnorePatterns::new(&[
+                "node_modules".to_string(),
+                "deployment".to_string(),
+            ]),
+        }
+    }
+
+    #[test]
+    fn test_ignore_patterns() {
+        let patterns = IgnorePatterns::new(&[
+            "node_modules".to_string(),
+            "archive*".to_string(),
+            "my?app".to_string(),
+            "deployment".to_string(),
+        ]);
+
+        assert!(patterns.is_match("node_modules"));
+        assert!(patterns.is_match("Archive-2023"));
+        assert!(!patterns.is_match("my.app"));
+        assert!(!patterns.is_match("deployment2"));
+        assert!(!IgnorePatterns::new(&[]).is_match("node_modules"));
+    }
+
+    #[test]
+    fn test_scan_app_roots_finds_nested_mpr_apps() {
+        let root = tempfile::tempdir().unwrap();
+        let nested_app = root.path().join("customer").join("portal");
+        fs::create_dir_all(&nested_app).unwrap();
+        fs::write(nested_app.join("Portal.mpr"), b"").unwrap();
+
+        let legacy_app = root.path().join("Legacy");
+        fs::create_dir_all(&legacy_app).unwrap();
+        fs::write(legacy_app.join(PROJECT_SETTINGS_FILE), b"{}").unwrap();
+
+        let apps = scan_app_roots(&[scan_root(root.path())], &app_scan_options());
+
+        assert_eq!(apps.len(), 2);
+        let portal = apps.iter().find(|app| app.name == "portal").unwrap();
+        assert!(portal.is_valid);
+        assert_eq!(
+            portal.mpr_path.as_deref(),
+            Some(nested_app.join("Portal.mpr").to_string_lossy().as_ref())
+        );
+        let legacy = apps.iter().find(|app| app.name == "Legacy").unwrap();
+        assert!(legacy.mpr_path.is_none());
+    }
+
+    #[test]
+    fn test_create_mendix_app_prefers_mpr_over_settings() {
+        let dir = tempfile::tempdir().unwrap();
+        crate::mendix::mpr::test_support::create_mpr_v1(
+            &dir.path().join("App.mpr"),
+            "10.4.0.1234",
+            &["MyFirstModule"],
+        );
+        fs::write(
+            dir.path().join(PROJECT_SETTINGS_FILE),
+            r#"{"settingsParts":[{"type":"Mendix.Settings, Version=9.24.0.0"}]}"#,
+        )
+        .unwrap();
+
+        let app = create_mendix_app("App".to_string(), dir.path().to_string_lossy().to_string());
+
+        assert_eq!(app.version.as_deref(), Some("10.4.0"));
+        assert_eq!(app.build_number.as_deref(), Some("1234"));
+        assert_eq!(app.modules, vec!["MyFirstModule"]);
+        assert!(app.project_id.is_some());
+    }
+
+    #[test]
+    fn test_create_mendix_app_falls_back_to_settings() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("App.mpr"), b"not a database").unwrap();
+        fs::write(
+            dir.path().join(PROJECT_SETTINGS_FILE),
+            r#"{"settingsParts":[{"type":"Mendix.Settings, Version=9.24.0.0"}]}"#,
+        )
+        .unwrap();
+
+        let app = create_mendix_app("App".to_string(), dir.path().to_string_lossy().to_string());
+
+        assert_eq!(app.version.as_deref(), Some("9.24.0"));
+        assert_eq!(app.build_number, None);
+        assert!(app.modules.is_empty());
+    }
+
+    #[test]
+    fn test_scan_app_roots_respects_ignore_patterns_and_depth() {
+        let root = tempfile::tempdir().unwrap();
+        let ignored = root.path().join("node_modules").join("pkg");
+        fs::create_dir_all(&ignored).unwrap();
+        fs::write(ignored.join("Ignored.mpr"), b"").unwrap();
+
+        let app = root.path().join("App");
+        fs::create_dir_all(app.join("deployment").join("copy")).unwrap();
+        fs::write(app.join("App.mpr"), b"").unwrap();
+        fs::write(app.join("deployment").join("copy").join("Copy.mpr"), b"").unwrap();
+
+        let too_deep = root
+            .path()
+            .join("a")
+            .join("b")
+            .join("c")
+            .join("d")
+            .join("e");
+        fs::create_dir_all(&too_deep).unwrap();
+        fs::write(too_deep.join("Deep.mpr"), b"").unwrap();
+
+        let apps = scan_app_roots(&[scan_root(root.path())], &app_scan_options());
+
+        let names: Vec<_> = apps.iter().map(|app| app.name.as_str()).collect();
+        assert_eq!(names, vec!["App"]);
+    }
+
+    #[test]
+    fn test_scan_install_roots_reports_root() {
+        let first = tempfile::tempdir().unwrap();
+        let second = tempfile::tempdir().unwrap();
+        create_fake_install(&first.path().join("10.4.0.1234"));
+        create_fake_install(&second.path().join("9.24.0.5678"));
+
+        let versions = scan_install_roots(&[scan_root(first.path()), scan_root(second.path())]);
+
+        assert_eq!(versions.len(), 2);
+        let v10 = find_installed_version(&versions, "10.4.0").unwrap();
+        assert!(v10.is_valid);
+        assert_eq!(v10.install_root, first.path().to_string_lossy());
+        let v9 = find_installed_version(&versions, "9.24.0").unwrap();
+        assert_eq!(v9.install_root, second.path().to_string_lossy());
+    }
+
+    #[test]
+    fn test_scan_install_roots_detects_portable_root() {
+        let parent = tempfile::tempdir().unwrap();
+        let portable = parent.path().join("10.6.1.9999");
+        create_fake_install(&portable);
+
+        let versions = scan_install_roots(&[scan_root(&portable)]);
+
+        assert_eq!(versions.len(), 1);
+        assert_eq!(versions[0].version, "10.6.1");
+        assert_eq!(versions[0].path, portable.to_string_lossy());
+    }
+
+    #[test]
+    fn test_scan_install_directory_outside_roots() {
+        let root = tempfile::tempdir().unwrap();
+        let custom = root.path().join("StudioPro");
+        let versioned = root.path().join("10.23.0.5000");
+        create_fake_install(&custom);
+        create_fake_install(&versioned);
+
+        let found = scan_install_directory(&custom, "10.24.0.7000").unwrap();
+        assert_eq!(
+            format_full_version(&found.version, found.build_number.as_deref()),
+            "10.24.0.7000"
+        );
+        assert!(found.is_valid);
+
+        assert!(scan_install_directory(&versioned, "10.24.0").is_none());
+        assert!(scan_install_directory(&versioned, "10.23.0").is_some());
+        assert!(scan_install_directory(&versioned, "10.23.0.6000").is_none());
+
+        let without_build = root.path().join("10.22.0");
+        create_fake_install(&without_build);
+        let found = scan_install_directory(&without_build, "10.22.0.4000").unwrap();
+        assert_eq!(found.build_number.as_deref(), Some("4000"));
+        assert!(scan_install_directory(&root.path().join("Missing"), "10.24.0").is_none());
+    }
+
+    #[test]
+    fn test_scan_install_roots_skips_missing_roots() {
+        let missing = Path::new("definitely-missing-install-root");
+        assert!(scan_install_roots(&[scan_root(missing)]).is_empty());
+    }
+}
@@ -0,0 +1,421 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use walkdir::WalkDir;
+
+use super::paths::{is_same_path, InstallRoot};
+use super::scanner::is_mendix_app_directory;
+
+/// Trash folder kept inside each app root, so trashing an app never crosses volumes
+pub const APP_TRASH_DIR: &str = ".app_trash";
+
+/// Metadata file stored next to each trashed app
+const TRASH_INFO_FILE: &str = "trash_info.json";
+
+/// Folder inside a trash entry holding the moved app
+const TRASHED_CONTENTS_DIR: &str = "app";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TrashedApp {
+    pub id: String,
+    pub name: String,
+    pub original_path: String,
+    pub deleted_at: DateTime<Utc>,
+}
+
+/// Trash directories of every existing app root
+pub fn get_app_trash_directories(roots: &[InstallRoot]) -> Vec<PathBuf> {
+    roots
+        .iter()
+        .filter(|root| root.exists)
+        .map(|root| Path::new(&root.path).join(APP_TRASH_DIR))
+        .collect()
+}
+
+/// Trash directory of the app root containing the app
+pub fn get_app_trash_directory(app_path: &Path, roots: &[InstallRoot]) -> Result<PathBuf, String> {
+    find_containing_root(app_path, roots)
+        .map(|root| Path::new(&root.path).join(APP_TRASH_DIR))
+        .ok_or_else(|| {
+            format!(
+                "App is outside the configured app roots: {}",
+                app_path.display()
+            )
+        })
+}
+
+/// Resolve a trash entry; ids come from the UI, so anything but a plain folder name
+/// directly inside the trash is rejected
+fn entry_dir(trash_dir: &Path, id: &str) -> Result<PathBuf, String> {
+    let mut components = Path::new(id).components();
+    let is_plain_name = matches!(
+        (components.next(), components.next()),
+        (Some(Component::Normal(_)), None)
+    );
+    let entry_path = trash_dir.join(id);
+
+    if !is_plain_name
+        || !entry_path
+            .parent()
+            .is_some_and(|parent| is_same_path(parent, trash_dir))
+    {
+        return Err(format!("Invalid trash id: {}", id));
+    }
+
+    Ok(entry_path)
+}
+
+fn find_containing_root<'a>(app_path: &Path, roots: &'a [InstallRoot]) -> Option<&'a InstallRoot> {
+    let app_path = app_path.canonicalize().ok()?;
+
+    roots.iter().find(|root| {
+        Path::new(&root.path)
+            .canonicalize()
+            .is_ok_and(|root| app_path != root && app_path.starts_with(&root))
+    })
+}
+
+/// Ensure the path is a Mendix app inside one of the app roots before touching it
+pub fn verify_deletable_app(app_path: &Path, roots: &[InstallRoot]) -> Result<(), String> {
+    if !app_path.is_dir() {
+        return Err(format!("App directory not found: {}", app_path.display()));
+    }
+
+    if !is_mendix_app_directory(app_path) {
+        return Err(format!("Not a Mendix app: {}", app_path.display()));
+    }
+
+    if app_path
+        .components()
+        .any(|component| component.as_os_str() == APP_TRASH_DIR)
+    {
+        return Err(format!(
+            "App is already in the trash: {}",
+            app_path.display()
+        ));
+    }
+
+    if find_containing_root(app_path, roots).is_none() {
+        return Err(format!(
+            "App is outside the configured app roots: {}",
+            app_path.display()
+        ));
+    }
+
+    Ok(())
+}
+
+fn copy_directory(source: &Path, target: &Path) -> Result<(), String> {
+    for entry in WalkDir::new(source) {
+        let entry = entry.map_err(|e| format!("Failed to read directory entry: {}", e))?;
+        let relative = entry
+            .path()
+            .strip_prefix(source)
+            .map_err(|e| format!("Failed to create relative path: {}", e))?;
+        let destination = target.join(relative);
+
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&destination)
+                .map_err(|e| format!("Failed to create directory: {}", e))?;
+        } else {
+            fs::copy(entry.path(), &destination)
+                .map_err(|e| format!("Failed to copy file: {}", e))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Move a directory, falling back to copy and delete across volumes
+fn move_directory(source: &Path, target: &Path) -> Result<(), String> {
+    if let Some(parent) = target.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create directory: {}", e))?;
+    }
+
+    if fs::rename(source, target).is_ok() {
+        return Ok(());
+    }
+
+    copy_directory(source, target).inspect_err(|_| {
+        let _ = fs::remove_dir_all(target);
+    })?;
+    fs::remove_dir_all(source).map_err(|e| format!("Failed to remove original directory: {}", e))
+}
+
+fn create_trash_id(name: &str, deleted_at: &DateTime<Utc>) -> String {
+    let safe_name: String = name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("{}_{}", deleted_at.timestamp_millis(), safe_name)
+}
+
+fn write_trash_info(trash_dir: &Path, info: &TrashedApp) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(info)
+        .map_err(|e| format!("Failed to serialize trash info: {}", e))?;
+    fs::write(
+        entry_dir(trash_dir, &info.id)?.join(TRASH_INFO_FILE),
+        content,
+    )
+    .map_err(|e| format!("Failed to write trash info: {}", e))
+}
+
+fn read_trash_info(entry_path: &Path) -> Option<TrashedApp> {
+    fs::read_to_string(entry_path.join(TRASH_INFO_FILE))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+}
+
+/// Record where a verified app came from, then move it into the trash; the entry is
+/// removed again when the move fails, leaving the app where it was
+pub fn move_app_to_trash(
+    app_path: &Path,
+    trash_dir: &Path,
+    deleted_at: DateTime<Utc>,
+) -> Result<TrashedApp, String> {
+    let name = app_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .ok_or_else(|| "Invalid app path".to_string())?;
+
+    let info = TrashedApp {
+        id: create_trash_id(&name, &deleted_at),
+        name,
+        original_path: app_path.to_string_lossy().to_string(),
+        deleted_at,
+    };
+    let entry_path = entry_dir(trash_dir, &info.id)?;
+
+    fs::create_dir_all(&entry_path).map_err(|e| format!("Failed to create trash entry: {}", e))?;
+    write_trash_info(trash_dir, &info)
+        .and_then(|_| move_directory(app_path, &entry_path.join(TRASHED_CONTENTS_DIR)))
+        .inspect_err(|_| {
+            let _ = fs::remove_dir_all(&entry_path);
+        })?;
+
+    Ok(info)
+}
+
+/// List trashed apps, newest first
+pub fn list_trash_entries(trash_dir: &Path) -> Vec<TrashedApp> {
+    list_all_trash_entries(std::slice::from_ref(&trash_dir.to_path_buf()))
+}
+
+/// List trashed apps of several trash directories, newest first
+pub fn list_all_trash_entries(trash_dirs: &[PathBuf]) -> Vec<TrashedApp> {
+    let mut entries: Vec<TrashedApp> = trash_dirs
+        .iter()
+        .filter_map(|trash_dir| fs::read_dir(trash_dir).ok())
+        .flat_map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|entry| read_trash_info(&entry.path()))
+        })
+        .collect();
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.deleted_at));
+    entries
+}
+
+/// Trash directory holding the entry with this id
+pub fn find_trash_directory<'a>(trash_dirs: &'a [PathBuf], id: &str) -> Result<&'a Path, String> {
+    trash_dirs
+        .iter()
+        .map(PathBuf::as_path)
+        .find(|trash_dir| find_trash_entry(trash_dir, id).is_ok())
+        .ok_or_else(|| format!("Trashed app not found: {}", id))
+}
+
+fn find_trash_entry(trash_dir: &Path, id: &str) -> Result<TrashedApp, String> {
+    read_trash_info(&entry_dir(trash_dir, id)?)
+        .ok_or_else(|| format!("Trashed app not found: {}", id))
+}
+
+/// Move a trashed app back to its original location
+pub fn restore_app_from_trash(trash_dir: &Path, id: &str) -> Result<TrashedApp, String> {
+    let info = find_trash_entry(trash_dir, id)?;
+    let original_path = Path::new(&info.original_path);
+
+    if original_path.exists() {
+        return Err(format!(
+            "Cannot restore, path already exists: {}",
+            info.original_path
+        ));
+    }
+
+    let entry_path = entry_dir(trash_dir, id)?;
+    move_directory(&entry_path.join(TRASHED_CONTENTS_DIR), original_path)?;
+    fs::remove_dir_all(&entry_path).map_err(|e| format!("Failed to remove trash entry: {}", e))?;
+
+    Ok(info)
+}
+
+/// Permanently delete a trashed app
+pub fn purge_trash_entry(trash_dir: &Path, id: &str) -> Result<TrashedApp, String> {
+    let info = find_trash_entry(trash_dir, id)?;
+    fs::remove_dir_all(entry_dir(trash_dir, id)?)
+        .map_err(|e| format!("Failed to purge trashed app: {}", e))?;
+    Ok(info)
+}
+
+/// Permanently delete trashed apps older than the retention period
+pub fn purge_expired_trash_entries(
+    trash_dir: &Path,
+    retention_days: u32,
+    now: DateTime<Utc>,
+) -> Vec<TrashedApp> {
+    let cutoff = now - Duration::days(i64::from(retention_days));
+
+    list_trash_entries(trash_dir)
+        .into_iter()
+        .filter(|entry| entry.deleted_at < cutoff)
+        .filter_map(|entry| purge_trash_entry(trash_dir, &entry.id).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::paths::InstallRootSource;
+    use super::super::scanner::{find_app_directories, AppScanOptions, IgnorePatterns};
+    use super::*;
+
+    fn scan_root(path: &Path) -> InstallRoot {
+        InstallRoot {
+            path: path.to_string_lossy().to_string(),
+            source: InstallRootSource::Configured,
+            exists: true,
+        }
+    }
+
+    fn create_app(root: &Path, name: &str) -> PathBuf {
+        let app_dir = root.join(name);
+        fs::create_dir_all(app_dir.join("deployment")).unwrap();
+        fs::write(app_dir.join(format!("{}.mpr", name)), b"mpr").unwrap();
+        fs::write(app_dir.join("deployment").join("data.txt"), b"data").unwrap();
+        app_dir
+    }
+
+    #[test]
+    fn test_verify_deletable_app_rejects_unknown_paths() {
+        let root = tempfile::tempdir().unwrap();
+        let outside = tempfile::tempdir().unwrap();
+        let roots = vec![scan_root(root.path())];
+
+        let plain_dir = root.path().join("NotAnApp");
+        fs::create_dir(&plain_dir).unwrap();
+        let outside_app = create_app(outside.path(), "Elsewhere");
+        let inside_app = create_app(root.path(), "Inside");
+        let trashed_app = create_app(&root.path().join(APP_TRASH_DIR).join("1"), "Trashed");
+
+        assert!(verify_deletable_app(&plain_dir, &roots).is_err());
+        assert!(verify_deletable_app(&outside_app, &roots).is_err());
+        assert!(verify_deletable_app(&trashed_app, &roots).is_err());
+        assert!(verify_deletable_app(root.path(), &roots).is_err());
+        assert!(verify_deletable_app(&inside_app, &roots).is_ok());
+    }
+
+    #[test]
+    fn test_trash_and_restore_round_trip() {
+        let root = tempfile::tempdir().unwrap();
+        let trash = tempfile::tempdir().unwrap();
+        let app_dir = create_app(root.path(), "MyApp");
+
+        let trashed = move_app_to_trash(&app_dir, trash.path(), Utc::now()).unwrap();
+        assert!(!app_dir.exists());
+        assert_eq!(list_trash_entries(trash.path()), vec![trashed.clone()]);
+
+        restore_app_from_trash(trash.path(), &trashed.id).unwrap();
+        assert!(app_dir.join("deployment").join("data.txt").exists());
+        assert!(list_trash_entries(trash.path()).is_empty());
+    }
+
+    #[test]
+    fn test_trash_stays_inside_the_app_root() {
+        let root = tempfile::tempdir().unwrap();
+        let roots = vec![scan_root(root.path())];
+        let app_dir = create_app(&root.path().join("Projects"), "MyApp");
+
+        let trash_dir = get_app_trash_directory(&app_dir, &roots).unwrap();
+        assert!(trash_dir.starts_with(root.path()));
+        assert_eq!(get_app_trash_directories(&roots), vec![trash_dir.clone()]);
+
+        let trashed = move_app_to_trash(&app_dir, &trash_dir, Utc::now()).unwrap();
+        let trash_dirs = get_app_trash_directories(&roots);
+        assert_eq!(list_all_trash_entries(&trash_dirs), vec![trashed.clone()]);
+        assert_eq!(
+            find_trash_directory(&trash_dirs, &trashed.id).unwrap(),
+            trash_dir
+        );
+        let options = AppScanOptions {
+            max_depth: 5,
+            ignore_patterns: IgnorePatterns::new(&[]),
+        };
+        assert!(find_app_directories(root.path(), &options).is_empty());
+    }
+
+    #[test]
+    fn test_failed_move_leaves_no_trash_entry() {
+        let root = tempfile::tempdir().unwrap();
+        let trash = tempfile::tempdir().unwrap();
+        let missing_app = root.path().join("Missing");
+
+        assert!(move_app_to_trash(&missing_app, trash.path(), Utc::now()).is_err());
+        assert!(fs::read_dir(trash.path()).unwrap().next().is_none());
+    }
+
+    #[test]
+    fn test_restore_refuses_to_overwrite() {
+        let root = tempfile::tempdir().unwrap();
+        let trash = tempfile::tempdir().unwrap();
+        let app_dir = create_app(root.path(), "MyApp");
+
+        let trashed = move_app_to_trash(&app_dir, trash.path(), Utc::now()).unwrap();
+        create_app(root.path(), "MyApp");
+
+        assert!(restore_app_from_trash(trash.path(), &trashed.id).is_err());
+    }
+
+    #[test]
+    fn test_trash_ids_cannot_escape_the_trash() {
+        let root = tempfile::tempdir().unwrap();
+        let trash_dir = root.path().join("trash");
+        fs::create_dir(&trash_dir).unwrap();
+        let sibling = create_app(root.path(), "Sibling");
+
+        for id in ["..", "../Sibling", "a/b", "", ".", "/tmp"] {
+            assert!(restore_app_from_trash(&trash_dir, id).is_err(), "{}", id);
+            assert!(purge_trash_entry(&trash_dir, id).is_err(), "{}", id);
+        }
+        assert!(sibling.join("Sibling.mpr").exists());
+    }
+
+    #[test]
+    fn test_purge_expired_trash_entries() {
+        let root = tempfile::tempdir().unwrap();
+        let trash = tempfile::tempdir().unwrap();
+        let now = Utc::now();
+        let old = move_app_to_trash(
+            &create_app(root.path(), "OldApp"),
+            trash.path(),
+            now - Duration::days(40),
+        )
+        .unwrap();
+        let recent = move_app_to_trash(
+            &create_app(root.path(), "RecentApp"),
+            trash.path(),
+            now - Duration::days(2),
+        )
+        .unwrap();
+
+        let purged = purge_expired_trash_entries(trash.path(), 30, now);
+
+        assert_eq!(purged, vec![old]);
+        assert_eq!(list_trash_entries(trash.path()), vec![recent]);
+    }
+}
@@ -0,0 +1,496 @@
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::broadcast;
+
+use crate::config::MendixPathsConfig;
+
+use super::execution::{
+    load_app_roots, load_install_roots, scan_installed_apps, scan_installed_versions,
+};
+use super::models::{MendixApp, MendixVersion};
+use super::scanner::{filter_valid_apps, filter_valid_versions, IgnorePatterns};
+
+/// Quiet period after the last filesystem event before rescanning
+const DEBOUNCE_INTERVAL: Duration = Duration::from_millis(750);
+
+/// Capacity of the in-process change channel
+const CHANGE_CHANNEL_CAPACITY: usize = 64;
+
+/// Interval of the fallback check while waiting for a change the watcher may never report
+const CHANGE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Interval of the fallback check while the watcher covers the path, in case an event is missed
+const WATCHED_CHANGE_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Interval for picking up roots that did not exist when they were last watched
+const ROOT_RECHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum MendixChangeEvent {
+    VersionAdded { version: MendixVersion },
+    VersionRemoved { version: MendixVersion },
+    AppAdded { app: MendixApp },
+    AppRemoved { app: MendixApp },
+    AppModified { app: MendixApp },
+}
+
+impl MendixChangeEvent {
+    /// Tauri event name the change is emitted under
+    pub fn event_name(&self) -> &'static str {
+        match self {
+            MendixChangeEvent::VersionAdded { .. } => "mendix-version-added",
+            MendixChangeEvent::VersionRemoved { .. } => "mendix-version-removed",
+            MendixChangeEvent::AppAdded { .. } => "mendix-app-added",
+            MendixChangeEvent::AppRemoved { .. } => "mendix-app-removed",
+            MendixChangeEvent::AppModified { .. } => "mendix-app-modified",
+        }
+    }
+}
+
+/// Versions and apps as seen by the last scan
+#[derive(Debug, Clone, Default)]
+pub struct MendixSnapshot {
+    pub versions: Vec<MendixVersion>,
+    pub apps: Vec<MendixApp>,
+}
+
+struct WatcherState {
+    watcher: RecommendedWatcher,
+    watched_paths: Vec<PathBuf>,
+    /// Roots that could not be watched, so the failure is only logged once
+    failed_paths: Vec<PathBuf>,
+}
+
+fn change_sender() -> &'static broadcast::Sender<MendixChangeEvent> {
+    static SENDER: OnceLock<broadcast::Sender<MendixChangeEvent>> = OnceLock::new();
+    SENDER.get_or_init(|| broadcast::channel(CHANGE_CHANNEL_CAPACITY).0)
+}
+
+fn watcher_state() -> &'static Mutex<Option<WatcherState>> {
+    static STATE: OnceLock<Mutex<Option<WatcherState>>> = OnceLock::new();
+    STATE.get_or_init(|| Mutex::new(None))
+}
+
+/// Receive every change detected by the watcher from now on
+pub fn subscribe_mendix_changes() -> broadcast::Receiver<MendixChangeEvent> {
+    change_sender().subscribe()
+}
+
+fn take_snapshot() -> MendixSnapshot {
+    MendixSnapshot {
+        versions: filter_valid_versions(scan_installed_versions()),
+        apps: filter_valid_apps(scan_installed_apps()),
+    }
+}
+
+fn is_app_modified(old: &MendixApp, new: &MendixApp) -> bool {
+    old.last_modified != new.last_modified
+        || old.version != new.version
+        || old.build_number != new.build_number
+}
+
+/// Compare two scans by path and describe what changed
+pub fn diff_snapshots(old: &MendixSnapshot, new: &MendixSnapshot) -> Vec<MendixChangeEvent> {
+    let old_versions: HashMap<&str, &MendixVersion> =
+        old.versions.iter().map(|v| (v.path.as_str(), v)).collect();
+    let new_versions: HashMap<&str, &MendixVersion> =
+        new.versions.iter().map(|v| (v.path.as_str(), v)).collect();
+    let old_apps: HashMap<&str, &MendixApp> =
+        old.apps.iter().map(|a| (a.path.as_str(), a)).collect();
+    let new_apps: HashMap<&str, &MendixApp> =
+        new.apps.iter().map(|a| (a.path.as_str(), a)).collect();
+
+    let versions_added = new
+        .versions
+        .iter()
+        .filter(|v| !old_versions.contains_key(v.path.as_str()))
+        .map(|v| MendixChangeEvent::VersionAdded { version: v.clone() });
+    let versions_removed = old
+        .versions
+        .iter()
+        .filter(|v| !new_versions.contains_key(v.path.as_str()))
+        .map(|v| MendixChangeEvent::VersionRemoved { version: v.clone() });
+    let apps_added = new
+        .apps
+        .iter()
+        .filter(|a| !old_apps.contains_key(a.path.as_str()))
+        .map(|a| MendixChangeEvent::AppAdded { app: a.clone() });
+    let apps_removed = old
+        .apps
+        .iter()
+        .filter(|a| !new_apps.contains_key(a.path.as_str()))
+        .map(|a| MendixChangeEvent::AppRemoved { app: a.clone() });
+    let apps_modified = new
+        .apps
+        .iter()
+        .filter(|a| {
+            old_apps
+                .get(a.path.as_str())
+                .is_some_and(|previous| is_app_modified(previous, a))
+        })
+        .map(|a| MendixChangeEvent::AppModified { app: a.clone() });
+
+    versions_added
+        .chain(versions_removed)
+        .chain(apps_added)
+        .chain(apps_removed)
+        .chain(apps_modified)
+        .collect()
+}
+
+/// Whether a filesystem event can affect the scan results
+///
+/// Only the part of the path below a watched root is matched against the ignore patterns.
+pub fn is_relevant_event(
+    event: &notify::Event,
+    roots: &[PathBuf],
+    ignore_patterns: &IgnorePatterns,
+) -> bool {
+    if matches!(event.kind, EventKind::Access(_)) {
+        return false;
+    }
+
+    event.paths.iter().any(|path| {
+        let relative = roots
+            .iter()
+            .find_map(|root| path.strip_prefix(root).ok())
+            .unwrap_or(path);
+
+        !relative
+            .components()
+            .any(|component| ignore_patterns.is_match(&component.as_os_str().to_string_lossy()))
+    })
+}
+
+fn publish_changes(app_handle: &AppHandle, changes: Vec<MendixChangeEvent>) {
+    for change in changes {
+        let _ = app_handle.emit(change.event_name(), change.clone());
+        let _ = change_sender().send(change);
+    }
+}
+
+fn run_watch_loop(events: Receiver<notify::Result<notify::Event>>, app_handle: AppHandle) {
+    let mut snapshot = take_snapshot();
+
+    while let Ok(first) = events.recv() {
+        let ignore_patterns = IgnorePatterns::new(
+            &MendixPathsConfig::load()
+                .unwrap_or_default()
+                .effective_app_ignore_patterns(),
+        );
+        let roots: Vec<PathBuf> = resolve_watch_targets()
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect();
+        let is_relevant = |event: notify::Result<notify::Event>| {
+            event.is_ok_and(|event| is_relevant_event(&event, &roots, &ignore_patterns))
+        };
+        let mut relevant = is_relevant(first);
+
+        loop {
+            match events.recv_timeout(DEBOUNCE_INTERVAL) {
+                Ok(next) => relevant |= is_relevant(next),
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(RecvTimeoutError::Disconnected) => return,
+            }
+        }
+
+        if relevant {
+            let next_snapshot = take_snapshot();
+            publish_changes(&app_handle, diff_snapshots(&snapshot, &next_snapshot));
+            snapshot = next_snapshot;
+        }
+    }
+}
+
+/// Install roots only change at the top level; apps can sit several levels deep
+fn resolve_watch_targets() -> Vec<(PathBuf, RecursiveMode)> {
+    let install_targets = load_install_roots()
+        .into_iter()
+        .map(|root| (root, RecursiveMode::NonRecursive));
+    let app_targets = load_app_roots()
+        .into_iter()
+        .map(|root| (root, RecursiveMode::Recursive));
+
+    install_targets
+        .chain(app_targets)
+        .filter(|(root, _)| root.exists)
+        .map(|(root, mode)| (PathBuf::from(root.path), mode))
+        .collect()
+}
+
+fn watch_target(state: &mut WatcherState, path: PathBuf, mode: RecursiveMode) {
+    match state.watcher.watch(&path, mode) {
+        Ok(()) => {
+            state.failed_paths.retain(|failed| failed != &path);
+            state.watched_paths.push(path);
+        }
+        Err(e) => {
+            if !state.failed_paths.contains(&path) {
+                println!("[Watcher] Failed to watch {}: {}", path.display(), e);
+                state.failed_paths.push(path);
+            }
+        }
+    }
+}
+
+fn apply_watch_targets(state: &mut WatcherState) {
+    for path in state.watched_paths.drain(..) {
+        let _ = state.watcher.unwatch(&path);
+    }
+
+    for (path, mode) in resolve_watch_targets() {
+        watch_target(state, path, mode);
+    }
+}
+
+/// Start watching install and app roots, emitting change events to the frontend
+pub fn start_mendix_watcher(app_handle: AppHandle) -> Result<(), String> {
+    let mut guard = watcher_state()
+        .lock()
+        .map_err(|e| format!("Failed to acquire watcher lock: {}", e))?;
+
+    if guard.is_some() {
+        return Ok(());
+    }
+
+    let (sender, receiver) = channel();
+    let watcher = notify::recommended_watcher(sender)
+        .map_err(|e| format!("Failed to create filesystem watcher: {}", e))?;
+    let mut state = WatcherState {
+        watcher,
+        watched_paths: Vec::new(),
+        failed_paths: Vec::new(),
+    };
+    apply_watch_targets(&mut state);
+    *guard = Some(state);
+
+    std::thread::spawn(move || run_watch_loop(receiver, app_handle));
+    std::thread::spawn(|| loop {
+        std::thread::sleep(ROOT_RECHECK_INTERVAL);
+        watch_new_roots();
+    });
+    Ok(())
+}
+
+/// Watch roots that appeared since the watch targets were last applied, leaving the
+/// existing watches in place
+fn watch_new_roots() {
+    if let Ok(mut guard) = watcher_state().lock() {
+        if let Some(state) = guard.as_mut() {
+            for (path, mode) in resolve_watch_targets() {
+                if !state.watched_paths.contains(&path) {
+                    watch_target(state, path, mode);
+                }
+            }
+        }
+    }
+}
+
+/// Re-read the configured roots after they change
+pub fn reload_watched_roots() {
+    if let Ok(mut guard) = watcher_state().lock() {
+        if let Some(state) = guard.as_mut() {
+            apply_watch_targets(state);
+        }
+    }
+}
+
+/// Whether the running watcher covers `path`, or any root when no path is given
+fn is_watching(path: Option<&Path>) -> bool {
+    watcher_state()
+        .lock()
+        .ok()
+        .and_then(|guard| {
+            guard.as_ref().map(|state| match path {
+                Some(path) => state
+                    .watched_paths
+                    .iter()
+                    .any(|watched| path.starts_with(watched)),
+                None => !state.watched_paths.is_empty(),
+            })
+        })
+        .unwrap_or(false)
+}
+
+/// Wait until a change satisfying `predicate` arrives or `is_done` confirms it, returning
+/// false on timeout. `is_done` runs on a blocking thread, often when the watcher is stopped
+/// or does not cover `watched_path` and only rarely otherwise.
+pub async fn wait_for_change<F, P>(
+    changes: &mut broadcast::Receiver<MendixChangeEvent>,
+    timeout: Duration,
+    watched_path: Option<&Path>,
+    predicate: F,
+    is_done: P,
+) -> bool
+where
+    F: Fn(&MendixChangeEvent) -> bool,
+    P: Fn() -> bool + Clone + Send + 'static,
+{
+    let poll_interval = if is_watching(watched_path) {
+        WATCHED_CHANGE_POLL_INTERVAL
+    } else {
+        CHANGE_POLL_INTERVAL
+    };
+
+    let wait = async {
+        let mut poll = tokio::time::interval(poll_interval);
+        let mut watching = true;
+
+        loop {
+            tokio::select! {
+                change = changes.recv(), if watching => match change {
+                    Ok(change) if predicate(&change) => return true,
+                    Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => {}
+                    Err(broadcast::error::RecvError::Closed) => watching = false,
+                },
+                _ = poll.tick() => {
+                    if tokio::task::spawn_blocking(is_done.clone()).await.unwrap_or(false) {
+                        return true;
+                    }
+                }
+            }
+        }
+    };
+
+    tokio::time::timeout(timeout, wait).await.unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn version(path: &str) -> MendixVersion {
+        MendixVersion {
+            version: "10.4.0".to_string(),
+            full_version: "10.4.0.1234".to_string(),
+            build_number: Some("1234".to_string()),
+            path: path.to_string(),
+            exe_path: format!("{}/modeler/studiopro.exe", path),
+            install_date: None,
+            is_valid: true,
+            install_root: "root".to_string(),
+        }
+    }
+
+    fn app(path: &str, version: &str) -> MendixApp {
+        MendixApp {
+            name: path.to_string(),
+            path: path.to_string(),
+            version: Some(version.to_string()),
+            build_number: None,
+            last_modified: None,
+            is_valid: true,
+            mpr_path: None,
+            project_id: None,
+            modules: Vec::new(),
+            git_status: None,
+        }
+    }
+
+    #[test]
+    fn test_diff_snapshots() {
+        let old = MendixSnapshot {
+            versions: vec![version("v1"), version("v2")],
+            apps: vec![app("a1", "10.4.0"), app("a2", "10.4.0")],
+        };
+        let new = MendixSnapshot {
+            versions: vec![version("v2"), version("v3")],
+            apps: vec![app("a2", "10.6.0"), app("a3", "10.4.0")],
+        };
+
+        let names: Vec<_> = diff_snapshots(&old, &new)
+            .iter()
+            .map(|change| change.event_name())
+            .collect();
+
+        assert_eq!(
+            names,
+            vec![
+                "mendix-version-added",
+                "mendix-version-removed",
+                "mendix-app-added",
+                "mendix-app-removed",
+                "mendix-app-modified",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_is_relevant_event_skips_ignored_folders() {
+        let root = PathBuf::from("deployment").join("apps");
+        let roots = vec![root.clone()];
+        let patterns = IgnorePatterns::new(&["deployment".to_string()]);
+        let ignored = notify::Event::new(EventKind::Any)
+            .add_path(root.join("MyApp").join("deployment").join("x"));
+        let relevant =
+            notify::Event::new(EventKind::Any).add_path(root.join("MyApp").join("MyApp.mpr"));
+
+        assert!(!is_relevant_event(&ignored, &roots, &patterns));
+        assert!(is_relevant_event(&relevant, &roots, &patterns));
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_change_matches_predicate() {
+        let (sender, mut receiver) = broadcast::channel(8);
+        sender
+            .send(MendixChangeEvent::AppAdded {
+                app: app("a1", "10.4.0"),
+            })
+            .unwrap();
+        sender
+            .send(MendixChangeEvent::VersionRemoved {
+                version: version("v1"),
+            })
+            .unwrap();
+
+        let removed = wait_for_change(
+            &mut receiver,
+            Duration::from_secs(1),
+            None,
+            |change| {
+                matches!(change, MendixChangeEvent::VersionRemoved { version } if version.path == "v1")
+            },
+            || false,
+        )
+        .await;
+        assert!(removed);
+
+        let timed_out = !wait_for_change(
+            &mut receiver,
+            Duration::from_millis(50),
+            None,
+            |_| true,
+            || false,
+        )
+        .await;
+        assert!(timed_out);
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_change_polls_without_events() {
+        let (sender, mut receiver) = broadcast::channel::<MendixChangeEvent>(8);
+        drop(sender);
+        let checks = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = checks.clone();
+
+        let confirmed = wait_for_change(
+            &mut receiver,
+            Duration::from_secs(5),
+            None,
+            |_| false,
+            move || counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst) >= 1,
+        )
+        .await;
+
+        assert!(confirmed);
+        assert_eq!(checks.load(std::sync::atomic::Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,288 @@
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use zip::ZipArchive;
+
+use crate::widget_parser::parse_xml_content;
+use crate::widget_preview::metadata::extract_widget_id_from_xml_content;
+
+/// Folder inside an app that holds deployed widget packages
+const WIDGETS_DIR: &str = "widgets";
+
+const PACKAGE_XML: &str = "package.xml";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PackagedWidget {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WidgetPackage {
+    pub file_name: String,
+    pub file_path: String,
+    pub file_size: u64,
+    pub package_name: Option<String>,
+    pub version: Option<String>,
+    pub widgets: Vec<PackagedWidget>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppWidgetInventory {
+    pub app_path: String,
+    pub packages: Vec<WidgetPackage>,
+}
+
+/// Client module name, version and widget file paths declared in `package.xml`
+#[derive(Debug, Default, PartialEq)]
+struct PackageManifest {
+    name: Option<String>,
+    version: Option<String>,
+    widget_files: Vec<String>,
+}
+
+fn read_attribute(element: &quick_xml::events::BytesStart, key: &[u8]) -> Option<String> {
+    element
+        .attributes()
+        .flatten()
+        .find(|attr| attr.key.as_ref() == key)
+        .and_then(|attr| attr.unescape_value().ok())
+        .map(|value| value.into_owned())
+}
+
+fn parse_package_manifest(xml_content: &str) -> Result<PackageManifest, String> {
+    let mut reader = Reader::from_str(xml_content);
+    reader.config_mut().trim_text(true);
+    let mut manifest = PackageManifest::default();
+
+    loop {
+        match reader.read_event() {
+            Ok(Event::Start(ref e)) | Ok(Event::Empty(ref e)) => match e.name().as_ref() {
+                b"clientModule" => {
+                    manifest.name = read_attribute(e, b"name");
+                    manifest.version = read_attribute(e, b"version");
+                }
+                b"widgetFile" => {
+                    if let Some(path) = read_attribute(e, b"path") {
+                        manifest.widget_files.push(path);
+                    }
+                }
+                _ => {}
+            },
+            Ok(Event::Eof) => break,
+            Err(e) => return Err(format!("Failed to parse package.xml: {}", e)),
+            _ => {}
+        }
+    }
+
+    Ok(manifest)
+}
+
+fn read_archive_entry<R: std::io::Read + std::io::Seek>(
+    archive: &mut ZipArchive<R>,
+    name: &str,
+) -> Result<String, String> {
+    let mut entry = archive
+        .by_name(name)
+        .map_err(|e| format!("Failed to find {} in package: {}", name, e))?;
+    let mut content = String::new();
+    entry
+        .read_to_string(&mut content)
+        .map_err(|e| format!("Failed to read {} from package: {}", name, e))?;
+    Ok(content)
+}
+
+fn read_packaged_widget<R: std::io::Read + std::io::Seek>(
+    archive: &mut ZipArchive<R>,
+    widget_file: &str,
+    fallback_name: Option<&str>,
+) -> Option<PackagedWidget> {
+    let xml_content = read_archive_entry(archive, widget_file).ok()?;
+    let id = extract_widget_id_from_xml_content(&xml_content)?;
+    let name = parse_xml_content(&xml_content)
+        .ok()
+        .map(|definition| definition.name)
+        .filter(|name| !name.is_empty())
+        .or_else(|| fallback_name.map(str::to_string))
+        .unwrap_or_else(|| id.clone());
+
+    Some(PackagedWidget { id, name })
+}
+
+fn read_package_contents(
+    mpk_path: &Path,
+) -> Result<(PackageManifest, Vec<PackagedWidget>), String> {
+    let file = File::open(mpk_path).map_err(|e| format!("Failed to open package: {}", e))?;
+    let mut archive =
+        ZipArchive::new(file).map_err(|e| format!("Failed to read package archive: {}", e))?;
+
+    let manifest = parse_package_manifest(&read_archive_entry(&mut archive, PACKAGE_XML)?)?;
+    let widgets = manifest
+        .widget_files
+        .iter()
+        .filter_map(|widget_file| {
+            read_packaged_widget(&mut archive, widget_file, manifest.name.as_deref())
+        })
+        .collect();
+
+    Ok((manifest, widgets))
+}
+
+/// Inspect a single `.mpk`, recording read failures on the entry instead of failing
+pub fn inspect_widget_package(mpk_path: &Path) -> WidgetPackage {
+    let file_name = mpk_path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let file_size = fs::metadata(mpk_path).map(|m| m.len()).unwrap_or(0);
+
+    let (package_name, version, widgets, error) = match read_package_contents(mpk_path) {
+        Ok((manifest, widgets)) => (manifest.name, manifest.version, widgets, None),
+        Err(e) => (None, None, Vec::new(), Some(e)),
+    };
+
+    WidgetPackage {
+        file_name,
+        file_path: mpk_path.to_string_lossy().to_string(),
+        file_size,
+        package_name,
+        version,
+        widgets,
+        error,
+    }
+}
+
+fn is_mpk_file(path: &Path) -> bool {
+    path.is_file()
+        && path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .map(|ext| ext.eq_ignore_ascii_case("mpk"))
+            .unwrap_or(false)
+}
+
+fn list_widget_packages(app_path: &Path) -> Vec<PathBuf> {
+    let mut packages: Vec<PathBuf> = fs::read_dir(app_path.join(WIDGETS_DIR))
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| is_mpk_file(path))
+                .collect()
+        })
+        .unwrap_or_default();
+    packages.sort();
+    packages
+}
+
+/// List every widget package deployed into an app's `widgets` folder
+pub fn collect_app_widget_inventory(app_path: &str) -> AppWidgetInventory {
+    let packages = list_widget_packages(Path::new(app_path))
+        .par_iter()
+        .map(|mpk_path| inspect_widget_package(mpk_path))
+        .collect();
+
+    AppWidgetInventory {
+        app_path: app_path.to_string(),
+        packages,
+    }
+}
+
+#[tauri::command]
+pub fn get_app_widget_inventory(app_paths: Vec<String>) -> Result<Vec<AppWidgetInventory>, String> {
+    Ok(app_paths
+        .iter()
+        .map(|app_path| collect_app_widget_inventory(app_path))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+
+    const PACKAGE_XML_CONTENT: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<package xmlns="http://www.mendix.com/package/1.0/">
+    <clientModule name="Calendar" version="2.3.1" xmlns="http://www.mendix.com/clientModule/1.0/">
+        <widgetFiles>
+            <widgetFile path="Calendar.xml"/>
+        </widgetFiles>
+    </clientModule>
+</package>"#;
+
+    const WIDGET_XML_CONTENT: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<widget id="com.example.calendar.Calendar" xmlns="http://www.mendix.com/widget/1.0/">
+    <name>Calendar</name>
+    <description>Shows events</description>
+    <properties/>
+</widget>"#;
+
+    fn write_mpk(path: &Path, entries: &[(&str, &str)]) {
+        let mut writer = zip::ZipWriter::new(File::create(path).unwrap());
+        for (name, content) in entries {
+            writer
+                .start_file(*name, SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap();
+    }
+
+    #[test]
+    fn test_parse_package_manifest() {
+        let manifest = parse_package_manifest(PACKAGE_XML_CONTENT).unwrap();
+
+        assert_eq!(manifest.name.as_deref(), Some("Calendar"));
+        assert_eq!(manifest.version.as_deref(), Some("2.3.1"));
+        assert_eq!(manifest.widget_files, vec!["Calendar.xml"]);
+    }
+
+    #[test]
+    fn test_collect_app_widget_inventory() {
+        let app_dir = tempfile::tempdir().unwrap();
+        let widgets_dir = app_dir.path().join(WIDGETS_DIR);
+        fs::create_dir(&widgets_dir).unwrap();
+        write_mpk(
+            &widgets_dir.join("Calendar.mpk"),
+            &[
+                (PACKAGE_XML, PACKAGE_XML_CONTENT),
+                ("Calendar.xml", WIDGET_XML_CONTENT),
+            ],
+        );
+        fs::write(widgets_dir.join("Broken.mpk"), b"not a zip").unwrap();
+        fs::write(widgets_dir.join("readme.txt"), b"ignored").unwrap();
+
+        let inventory = collect_app_widget_inventory(&app_dir.path().to_string_lossy());
+
+        assert_eq!(inventory.packages.len(), 2);
+        let broken = &inventory.packages[0];
+        assert_eq!(broken.file_name, "Broken.mpk");
+        assert!(broken.error.is_some());
+
+        let calendar = &inventory.packages[1];
+        assert_eq!(calendar.version.as_deref(), Some("2.3.1"));
+        assert!(calendar.file_size > 0);
+        assert_eq!(
+            calendar.widgets,
+            vec![PackagedWidget {
+                id: "com.example.calendar.Calendar".to_string(),
+                name: "Calendar".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_collect_app_widget_inventory_without_widgets_folder() {
+        let app_dir = tempfile::tempdir().unwrap();
+
+        let inventory = collect_app_widget_inventory(&app_dir.path().to_string_lossy());
+
+        assert!(inventory.packages.is_empty());
+    }
+}
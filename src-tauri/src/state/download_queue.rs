@@ -0,0 +1,252 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_MAX_CONCURRENT_DOWNLOADS: usize = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DownloadQueueStatus {
+    Queued,
+    Downloading,
+    Verifying,
+    /// Downloaded and waiting for another installer to finish
+    Waiting,
+    Installing,
+    Failed,
+}
+
+impl DownloadQueueStatus {
+    /// Whether the item is being worked on
+    pub fn is_active(self) -> bool {
+        matches!(
+            self,
+            DownloadQueueStatus::Downloading
+                | DownloadQueueStatus::Verifying
+                | DownloadQueueStatus::Waiting
+                | DownloadQueueStatus::Installing
+        )
+    }
+
+    /// Whether the item occupies one of the concurrent download slots; installs run one
+    /// at a time on their own, so waiting and installing items free the slot for the next download
+    pub fn uses_download_slot(self) -> bool {
+        matches!(
+            self,
+            DownloadQueueStatus::Downloading | DownloadQueueStatus::Verifying
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadQueueItem {
+    pub version: String,
+    pub status: DownloadQueueStatus,
+    pub error: Option<String>,
+    pub enqueued_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DownloadQueueState {
+    pub items: Vec<DownloadQueueItem>,
+    pub max_concurrent: usize,
+}
+
+impl Default for DownloadQueueState {
+    fn default() -> Self {
+        Self {
+            items: Vec::new(),
+            max_concurrent: DEFAULT_MAX_CONCURRENT_DOWNLOADS,
+        }
+    }
+}
+
+impl DownloadQueueState {
+    fn find_mut(&mut self, version: &str) -> Option<&mut DownloadQueueItem> {
+        self.items.iter_mut().find(|item| item.version == version)
+    }
+
+    pub fn status_of(&self, version: &str) -> Option<DownloadQueueStatus> {
+        self.items
+            .iter()
+            .find(|item| item.version == version)
+            .map(|item| item.status)
+    }
+
+    /// Append a version; returns false when it is already queued
+    pub fn enqueue(&mut self, version: &str, now: DateTime<Utc>) -> bool {
+        if self.status_of(version).is_some() {
+            return false;
+        }
+
+        self.items.push(DownloadQueueItem {
+            version: version.to_string(),
+            status: DownloadQueueStatus::Queued,
+            error: None,
+            enqueued_at: now,
+        });
+        true
+    }
+
+    pub fn remove(&mut self, version: &str) -> Option<DownloadQueueItem> {
+        let index = self.items.iter().position(|item| item.version == version)?;
+        Some(self.items.remove(index))
+    }
+
+    /// Move an item to a new position; indices past the end move it to the back
+    pub fn move_item(&mut self, version: &str, index: usize) -> Result<(), String> {
+        let item = self
+            .remove(version)
+            .ok_or_else(|| format!("Version not in download queue: {}", version))?;
+        let index = index.min(self.items.len());
+        self.items.insert(index, item);
+        Ok(())
+    }
+
+    /// Update the status of an item; unknown versions are ignored since they may
+    /// have been removed while running
+    pub fn set_status(
+        &mut self,
+        version: &str,
+        status: DownloadQueueStatus,
+        error: Option<String>,
+    ) {
+        if let Some(item) = self.find_mut(version) {
+            item.status = status;
+            item.error = error;
+        }
+    }
+
+    pub fn retry(&mut self, version: &str) -> Result<(), String> {
+        match self.find_mut(version) {
+            Some(item) if item.status == DownloadQueueStatus::Failed => {
+                item.status = DownloadQueueStatus::Queued;
+                item.error = None;
+                Ok(())
+            }
+            Some(_) => Err(format!("Download of {} has not failed", version)),
+            None => Err(format!("Version not in download queue: {}", version)),
+        }
+    }
+
+    pub fn set_max_concurrent(&mut self, max_concurrent: usize) {
+        self.max_concurrent = max_concurrent.max(1);
+    }
+
+    pub fn active_count(&self) -> usize {
+        self.items
+            .iter()
+            .filter(|item| item.status.is_active())
+            .count()
+    }
+
+    pub fn downloading_count(&self) -> usize {
+        self.items
+            .iter()
+            .filter(|item| item.status.uses_download_slot())
+            .count()
+    }
+
+    /// Mark queued items as downloading, in queue order, up to the concurrency limit
+    pub fn start_next(&mut self) -> Vec<String> {
+        let free_slots = self.max_concurrent.saturating_sub(self.downloading_count());
+
+        self.items
+            .iter_mut()
+            .filter(|item| item.status == DownloadQueueStatus::Queued)
+            .take(free_slots)
+            .map(|item| {
+                item.status = DownloadQueueStatus::Downloading;
+                item.version.clone()
+            })
+            .collect()
+    }
+
+    /// Requeue items that were running when the app closed; downloads resume from their partial file
+    pub fn reset_interrupted(&mut self) {
+        self.items
+            .iter_mut()
+            .filter(|item| item.status.is_active())
+            .for_each(|item| item.status = DownloadQueueStatus::Queued);
+    }
+
+    pub fn versions(&self) -> Vec<String> {
+        self.items.iter().map(|item| item.version.clone()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn queue_of(versions: &[&str]) -> DownloadQueueState {
+        let mut queue = DownloadQueueState::default();
+        for version in versions {
+            queue.enqueue(version, Utc::now());
+        }
+        queue
+    }
+
+    #[test]
+    fn test_enqueue_skips_duplicates() {
+        let mut queue = queue_of(&["10.4.0"]);
+
+        assert!(!queue.enqueue("10.4.0", Utc::now()));
+        assert!(queue.enqueue("10.5.0", Utc::now()));
+        assert_eq!(queue.versions(), vec!["10.4.0", "10.5.0"]);
+    }
+
+    #[test]
+    fn test_start_next_respects_concurrency_and_order() {
+        let mut queue = queue_of(&["10.4.0", "10.5.0", "10.6.0"]);
+        queue.move_item("10.6.0", 0).unwrap();
+
+        assert_eq!(queue.start_next(), vec!["10.6.0", "10.4.0"]);
+        assert!(queue.start_next().is_empty());
+
+        queue.set_status("10.6.0", DownloadQueueStatus::Failed, Some("boom".into()));
+        assert_eq!(queue.start_next(), vec!["10.5.0"]);
+    }
+
+    #[test]
+    fn test_installing_items_free_their_download_slot() {
+        let mut queue = queue_of(&["10.4.0", "10.5.0", "10.6.0"]);
+        queue.set_max_concurrent(1);
+
+        assert_eq!(queue.start_next(), vec!["10.4.0"]);
+        assert!(queue.start_next().is_empty());
+
+        queue.set_status("10.4.0", DownloadQueueStatus::Installing, None);
+        assert_eq!(queue.start_next(), vec!["10.5.0"]);
+
+        queue.set_status("10.5.0", DownloadQueueStatus::Waiting, None);
+        assert_eq!(queue.start_next(), vec!["10.6.0"]);
+        assert_eq!(queue.active_count(), 3);
+    }
+
+    #[test]
+    fn test_retry_only_failed_items() {
+        let mut queue = queue_of(&["10.4.0"]);
+
+        assert!(queue.retry("10.4.0").is_err());
+        queue.set_status("10.4.0", DownloadQueueStatus::Failed, Some("boom".into()));
+        queue.retry("10.4.0").unwrap();
+
+        assert_eq!(queue.status_of("10.4.0"), Some(DownloadQueueStatus::Queued));
+        assert_eq!(queue.items[0].error, None);
+    }
+
+    #[test]
+    fn test_reset_interrupted() {
+        let mut queue = queue_of(&["10.4.0", "10.5.0"]);
+        queue.set_max_concurrent(1);
+        queue.start_next();
+        queue.set_status("10.4.0", DownloadQueueStatus::Installing, None);
+
+        queue.reset_interrupted();
+
+        assert_eq!(queue.active_count(), 0);
+        assert_eq!(queue.start_next(), vec!["10.4.0"]);
+    }
+}
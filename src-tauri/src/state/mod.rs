@@ -1,3 +1,4 @@
+mod download_queue;
 mod selection;
 mod version_operations;
 
@@ -6,6 +7,7 @@ pub use selection::{
     init_selection_from_storage, is_selected, remove_from_selection, remove_from_selection_with_save,
     set_selection, toggle_selection, toggle_selection_with_save, SelectionState,
 };
+pub use download_queue::{DownloadQueueItem, DownloadQueueState, DownloadQueueStatus};
 pub use version_operations::*;
 
 use std::sync::Mutex;
@@ -14,4 +16,5 @@ use std::sync::Mutex;
 pub struct AppState {
     pub selection: Mutex<SelectionState>,
     pub version_ops: Mutex<VersionOpsState>,
+    pub download_queue: Mutex<DownloadQueueState>,
 }
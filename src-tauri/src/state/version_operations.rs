@@ -2,7 +2,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use tauri::State;
 
-use crate::state::AppState;
+use crate::state::{AppState, DownloadQueueStatus};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -10,6 +10,7 @@ pub enum VersionOperation {
     Launch,
     Uninstall,
     Download,
+    Queued,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -27,6 +28,7 @@ pub struct VersionLoadingFlags {
     pub is_launching: bool,
     pub is_uninstalling: bool,
     pub is_downloading: bool,
+    pub is_queued: bool,
 }
 
 #[derive(Default)]
@@ -61,6 +63,7 @@ impl VersionOpsState {
                 is_launching: state.operation == VersionOperation::Launch,
                 is_uninstalling: state.operation == VersionOperation::Uninstall,
                 is_downloading: state.operation == VersionOperation::Download,
+                is_queued: state.operation == VersionOperation::Queued,
             },
             _ => VersionLoadingFlags::default(),
         }
@@ -77,6 +80,34 @@ impl VersionOpsState {
         }
     }
 
+    /// Mirror a download queue item; `None` means the version left the queue.
+    /// A launch or uninstall of the same version is never overwritten or cleared.
+    pub fn set_queue_status(
+        &mut self,
+        version_id: String,
+        status: Option<DownloadQueueStatus>,
+    ) -> HashMap<String, VersionLoadingState> {
+        let is_queue_operation = self.states.get(&version_id).is_none_or(|state| {
+            matches!(
+                state.operation,
+                VersionOperation::Download | VersionOperation::Queued
+            )
+        });
+        if !is_queue_operation {
+            return self.states.clone();
+        }
+
+        match status {
+            Some(DownloadQueueStatus::Queued) => {
+                self.set_operation(version_id, VersionOperation::Queued, true)
+            }
+            Some(status) if status.is_active() => {
+                self.set_operation(version_id, VersionOperation::Download, true)
+            }
+            _ => self.set_operation(version_id, VersionOperation::Download, false),
+        }
+    }
+
     pub fn get_all(&self) -> HashMap<String, VersionLoadingState> {
         self.states.clone()
     }
@@ -200,4 +231,32 @@ mod tests {
         assert!(all.contains_key("v1"));
         assert!(all.contains_key("v2"));
     }
+
+    #[test]
+    fn test_set_queue_status() {
+        let mut state = VersionOpsState::default();
+
+        state.set_queue_status("v1".to_string(), Some(DownloadQueueStatus::Queued));
+        assert!(state.get_loading_state("v1").is_queued);
+
+        state.set_queue_status("v1".to_string(), Some(DownloadQueueStatus::Verifying));
+        let flags = state.get_loading_state("v1");
+        assert!(flags.is_downloading);
+        assert!(!flags.is_queued);
+
+        state.set_queue_status("v1".to_string(), Some(DownloadQueueStatus::Failed));
+        assert!(!state.get_all().contains_key("v1"));
+    }
+
+    #[test]
+    fn test_set_queue_status_keeps_other_operations() {
+        let mut state = VersionOpsState::default();
+
+        state.set_operation("v1".to_string(), VersionOperation::Uninstall, true);
+        state.set_queue_status("v1".to_string(), Some(DownloadQueueStatus::Failed));
+        assert!(state.is_busy("v1"));
+
+        state.set_queue_status("v1".to_string(), None);
+        assert!(state.get_loading_state("v1").is_uninstalling);
+    }
 }
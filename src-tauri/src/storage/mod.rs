@@ -1,5 +1,7 @@
 use crate::data_processing::mendix_filters::Widget;
-use crate::web_scraper::{DownloadableVersion, DownloadableVersionsCache};
+use crate::state::DownloadQueueState;
+use crate::web_scraper::{CatalogueImport, DownloadableVersion, DownloadableVersionsCache};
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::HashMap;
@@ -26,6 +28,7 @@ pub enum StorageKey {
     WidgetOrder,
     DownloadableVersionsCache,
     Locale,
+    DownloadQueue,
 }
 
 impl StorageKey {
@@ -42,6 +45,7 @@ impl StorageKey {
             StorageKey::WidgetOrder => "widgetOrder",
             StorageKey::DownloadableVersionsCache => "downloadableVersionsCache",
             StorageKey::Locale => "locale",
+            StorageKey::DownloadQueue => "downloadQueue",
         }
     }
 }
@@ -62,6 +66,7 @@ impl TryFrom<&str> for StorageKey {
             "widgetOrder" => Ok(StorageKey::WidgetOrder),
             "downloadableVersionsCache" => Ok(StorageKey::DownloadableVersionsCache),
             "locale" => Ok(StorageKey::Locale),
+            "downloadQueue" => Ok(StorageKey::DownloadQueue),
             _ => Err(format!("Unknown storage key: {}", value)),
         }
     }
@@ -86,6 +91,7 @@ pub struct AppState {
     pub widget_order: Option<Value>,
     pub downloadable_versions_cache: Option<Value>,
     pub locale: Option<String>,
+    pub download_queue: Option<Value>,
 }
 
 impl Default for AppState {
@@ -102,6 +108,7 @@ impl Default for AppState {
             widget_order: None,
             downloadable_versions_cache: None,
             locale: None,
+            download_queue: None,
         }
     }
 }
@@ -120,6 +127,7 @@ impl AppState {
             StorageKey::WidgetOrder => self.widget_order.clone(),
             StorageKey::DownloadableVersionsCache => self.downloadable_versions_cache.clone(),
             StorageKey::Locale => self.locale.clone().map(Value::String),
+            StorageKey::DownloadQueue => self.download_queue.clone(),
         }
     }
 
@@ -150,6 +158,7 @@ impl AppState {
                     self.locale = Some(s.to_string());
                 }
             }
+            StorageKey::DownloadQueue => self.download_queue = Some(value),
         }
     }
 }
@@ -350,6 +359,7 @@ pub fn add_widget_and_save(caption: String, path: String) -> Result<Widget, Stri
 fn merge_downloadable_versions(
     cached: Vec<DownloadableVersion>,
     fresh: Vec<DownloadableVersion>,
+    now: DateTime<Utc>,
 ) -> Vec<DownloadableVersion> {
     let mut version_map: HashMap<String, DownloadableVersion> = cached
         .into_iter()
@@ -357,7 +367,26 @@ fn merge_downloadable_versions(
         .collect();
 
     for v in fresh {
-        version_map.insert(v.version.clone(), v);
+        let merged = match version_map.get(&v.version) {
+            // A scraped row has no build number; keep the one found earlier
+            Some(existing) if v.build_number.is_none() && existing.build_number.is_some() => {
+                DownloadableVersion {
+                    first_seen_at: existing.first_seen_at,
+                    build_number: existing.build_number.clone(),
+                    artifacts: existing.artifacts.clone(),
+                    ..v
+                }
+            }
+            Some(existing) => DownloadableVersion {
+                first_seen_at: existing.first_seen_at,
+                ..v
+            },
+            None => DownloadableVersion {
+                first_seen_at: Some(now),
+                ..v
+            },
+        };
+        version_map.insert(merged.version.clone(), merged);
     }
 
     let mut versions: Vec<DownloadableVersion> = version_map.into_values().collect();
@@ -386,12 +415,42 @@ fn compare_version_strings(a: &str, b: &str) -> std::cmp::Ordering {
 }
 
 fn parse_cache_from_value(value: &Value) -> Result<Vec<DownloadableVersion>, String> {
-    serde_json::from_value::<DownloadableVersionsCache>(value.clone())
-        .map(|cache| cache.versions)
-        .or_else(|_| {
-            serde_json::from_value::<Vec<DownloadableVersion>>(value.clone())
-                .map_err(|e| format!("Failed to parse cache: {}", e))
-        })
+    parse_catalogue_from_value(value).map(|cache| cache.versions)
+}
+
+/// Parse a stored cache or catalogue file; legacy caches are a bare list of versions
+pub fn parse_catalogue_from_value(value: &Value) -> Result<DownloadableVersionsCache, String> {
+    serde_json::from_value::<DownloadableVersionsCache>(value.clone()).or_else(|_| {
+        serde_json::from_value::<Vec<DownloadableVersion>>(value.clone())
+            .map(|versions| DownloadableVersionsCache {
+                versions,
+                ..DownloadableVersionsCache::default()
+            })
+            .map_err(|e| format!("Failed to parse cache: {}", e))
+    })
+}
+
+fn load_catalogue_from_state(state: &AppState) -> DownloadableVersionsCache {
+    state
+        .get(StorageKey::DownloadableVersionsCache)
+        .and_then(|value| parse_catalogue_from_value(&value).ok())
+        .unwrap_or_default()
+}
+
+fn save_catalogue_to_state(
+    state: &mut AppState,
+    cache: &DownloadableVersionsCache,
+) -> Result<(), String> {
+    state.set(
+        StorageKey::DownloadableVersionsCache,
+        serde_json::to_value(cache).map_err(|e| format!("Failed to serialize cache: {}", e))?,
+    );
+    save_state_to_file(state)
+}
+
+/// The whole downloadable versions cache, including refresh and import details
+pub fn load_downloadable_versions_catalogue() -> DownloadableVersionsCache {
+    load_catalogue_from_state(&load_state_from_file().unwrap_or_default())
 }
 
 #[tauri::command]
@@ -413,26 +472,70 @@ pub fn merge_and_save_downloadable_versions(
         .map_err(|e| format!("Failed to acquire storage lock: {}", e))?;
 
     let mut state = load_state_from_file().unwrap_or_default();
+    let cached = load_catalogue_from_state(&state);
 
-    let cached = state
-        .get(StorageKey::DownloadableVersionsCache)
-        .and_then(|v| parse_cache_from_value(&v).ok())
-        .unwrap_or_default();
-
-    let merged = merge_downloadable_versions(cached, fresh);
+    let now = Utc::now();
+    let merged = merge_downloadable_versions(cached.versions, fresh, now);
 
     let cache = DownloadableVersionsCache {
         versions: merged.clone(),
+        last_refreshed_at: Some(now),
+        ..cached
     };
-    state.set(
-        StorageKey::DownloadableVersionsCache,
-        serde_json::to_value(cache).map_err(|e| format!("Failed to serialize cache: {}", e))?,
-    );
-    save_state_to_file(&state)?;
+    save_catalogue_to_state(&mut state, &cache)?;
 
     Ok(merged)
 }
 
+/// Merge an imported catalogue into the cache like a scrape would. The cache counts as
+/// refreshed when the catalogue was, and `source` is recorded as the last import.
+pub fn merge_and_save_imported_catalogue(
+    catalogue: DownloadableVersionsCache,
+    source: &str,
+) -> Result<DownloadableVersionsCache, String> {
+    let _lock = storage_mutex()
+        .lock()
+        .map_err(|e| format!("Failed to acquire storage lock: {}", e))?;
+
+    let mut state = load_state_from_file().unwrap_or_default();
+    let cached = load_catalogue_from_state(&state);
+
+    let now = Utc::now();
+    let versions_imported = catalogue.versions.len();
+    let cache = DownloadableVersionsCache {
+        versions: merge_downloadable_versions(cached.versions, catalogue.versions, now),
+        last_refreshed_at: cached.last_refreshed_at.max(catalogue.last_refreshed_at),
+        last_import: Some(CatalogueImport {
+            source: source.to_string(),
+            imported_at: now,
+            versions_imported,
+        }),
+    };
+    save_catalogue_to_state(&mut state, &cache)?;
+
+    Ok(cache)
+}
+
+/// Update one cached version in place, leaving refresh details untouched
+pub fn update_cached_downloadable_version<F>(version: &str, update: F) -> Result<(), String>
+where
+    F: FnOnce(DownloadableVersion) -> DownloadableVersion,
+{
+    let _lock = storage_mutex()
+        .lock()
+        .map_err(|e| format!("Failed to acquire storage lock: {}", e))?;
+
+    let mut state = load_state_from_file().unwrap_or_default();
+    let mut cache = load_catalogue_from_state(&state);
+
+    let Some(index) = cache.versions.iter().position(|v| v.version == version) else {
+        return Ok(());
+    };
+    let cached = cache.versions.remove(index);
+    cache.versions.insert(index, update(cached));
+    save_catalogue_to_state(&mut state, &cache)
+}
+
 #[tauri::command]
 pub fn clear_downloadable_versions_cache() -> Result<(), String> {
     let _lock = storage_mutex()
@@ -440,10 +543,24 @@ pub fn clear_downloadable_versions_cache() -> Result<(), String> {
         .map_err(|e| format!("Failed to acquire storage lock: {}", e))?;
 
     let mut state = load_state_from_file().unwrap_or_default();
-    let cache = DownloadableVersionsCache { versions: vec![] };
+    let cache = DownloadableVersionsCache::default();
     state.set(
         StorageKey::DownloadableVersionsCache,
         serde_json::to_value(cache).map_err(|e| format!("Failed to serialize cache: {}", e))?,
     );
     save_state_to_file(&state)
 }
+
+pub fn load_download_queue() -> DownloadQueueState {
+    load_state_from_file()
+        .unwrap_or_default()
+        .get(StorageKey::DownloadQueue)
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_download_queue(queue: &DownloadQueueState) -> Result<(), String> {
+    let value = serde_json::to_value(queue)
+        .map_err(|e| format!("Failed to serialize download queue: {}", e))?;
+    save_specific_state_typed(StorageKey::DownloadQueue, value)
+}
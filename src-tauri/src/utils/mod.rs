@@ -24,7 +24,7 @@ pub struct FileTreeNode {
     pub source_path: PathBuf,
 }
 
-fn find_highest_version_in_dist(widget_path: &str) -> Option<PathBuf> {
+pub(crate) fn find_highest_version_in_dist(widget_path: &str) -> Option<PathBuf> {
     let dist_path = Path::new(widget_path).join("dist");
 
     if !dist_path.exists() || !dist_path.is_dir() {
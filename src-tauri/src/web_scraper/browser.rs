@@ -6,10 +6,12 @@ use std::sync::Arc;
 use std::time::Duration;
 use tokio::time::timeout;
 
+use super::browser_detection::resolve_browser_executable;
 use super::config::{
-    CHROME_EXECUTABLE_PATH, CLICK_DEBOUNCE_MS, CLICK_NAVIGATION_DELAY_MS, NAVIGATION_TIMEOUT_SECS,
-    PAGE_CHANGE_POLL_MS,
+    CLICK_DEBOUNCE_MS, CLICK_NAVIGATION_DELAY_MS, NAVIGATION_TIMEOUT_SECS, PAGE_CHANGE_POLL_MS,
 };
+use super::network::{browser_proxy_args, load_network_settings};
+use super::selectors::SelectorProfile;
 
 /// Browser session wrapper that manages browser lifecycle
 pub struct BrowserSession {
@@ -52,9 +54,10 @@ impl BrowserSession {
 async fn create_browser_instance() -> Result<(Browser, chromiumoxide::handler::Handler), String> {
     let temp_dir = std::env::temp_dir().join(format!("kiraichi-chrome-{}", std::process::id()));
     let user_data_dir = temp_dir.to_string_lossy().to_string();
+    let browser_executable = resolve_browser_executable()?;
 
     let config = BrowserConfig::builder()
-        .chrome_executable(CHROME_EXECUTABLE_PATH)
+        .chrome_executable(browser_executable)
         .user_data_dir(&user_data_dir)
         .args(vec![
             "--no-sandbox",
@@ -69,6 +72,7 @@ async fn create_browser_instance() -> Result<(Browser, chromiumoxide::handler::H
             "--disable-features=VizDisplayCompositor",
             "--headless=new",
         ])
+        .args(browser_proxy_args(&load_network_settings())?)
         .build()?;
 
     Browser::launch(config)
@@ -137,28 +141,14 @@ pub async fn wait_for_element_with_timeout(
 }
 
 /// Handle privacy/cookie modal if present
-pub async fn handle_privacy_modal_if_present(page: &Page) -> Result<(), String> {
-    let modal_selectors = [
-        "[data-testid='uc-default-wall']",
-        ".cookie-banner",
-        "#cookie-banner",
-        "[class*='cookie']",
-        "[class*='privacy']",
-    ];
-
-    for modal_selector in &modal_selectors {
-        if page.find_element(*modal_selector).await.is_ok() {
-            let button_selectors = [
-                "[data-testid='uc-deny-all-button']",
-                "[data-testid='uc-reject-all-button']",
-                "button[class*='reject']",
-                "button[class*='deny']",
-                "button:contains('Reject')",
-                "button:contains('Deny')",
-            ];
-
-            for button_selector in &button_selectors {
-                if let Ok(button) = page.find_element(*button_selector).await {
+pub async fn handle_privacy_modal_if_present(
+    page: &Page,
+    profile: &SelectorProfile,
+) -> Result<(), String> {
+    for modal_selector in &profile.privacy_modals {
+        if page.find_element(modal_selector.as_str()).await.is_ok() {
+            for button_selector in &profile.privacy_reject_buttons {
+                if let Ok(button) = page.find_element(button_selector.as_str()).await {
                     let _ = button.click().await;
                     return Ok(());
                 }
@@ -178,21 +168,18 @@ pub async fn extract_element_html(element: &Element) -> Result<String, String> {
         .ok_or_else(|| "Element has no HTML content".to_string())
 }
 
-/// Click next page button for pagination
-pub async fn click_next_page_button(page: &Page) -> Result<(), String> {
-    let selectors = [
-        "button[aria-label='Go to next page']",
-        ".pagination-button[aria-label='Go to next page']",
-        "button.pagination-button:nth-child(3)",
-    ];
-
-    for selector in &selectors {
+/// Click next page button for pagination; returns false when already on the last page
+pub async fn click_next_page_button(
+    page: &Page,
+    profile: &SelectorProfile,
+) -> Result<bool, String> {
+    for selector in &profile.next_page_buttons {
         match wait_for_element_with_timeout(page, selector, 10).await {
             Ok(elements) => {
                 if let Some(button) = elements.into_iter().next() {
                     if let Ok(disabled) = button.attribute("disabled").await {
                         if disabled.is_some() {
-                            return Err("Next page button is disabled".to_string());
+                            return Ok(false);
                         }
                     }
 
@@ -203,7 +190,7 @@ pub async fn click_next_page_button(page: &Page) -> Result<(), String> {
 
                     tokio::time::sleep(Duration::from_millis(CLICK_NAVIGATION_DELAY_MS)).await;
 
-                    return Ok(());
+                    return Ok(true);
                 }
             }
             Err(_) => continue,
@@ -214,7 +201,11 @@ pub async fn click_next_page_button(page: &Page) -> Result<(), String> {
 }
 
 /// Navigate to a specific page number in paginated content
-pub async fn navigate_to_page(page: &Page, target_page: u32) -> Result<(), String> {
+pub async fn navigate_to_page(
+    page: &Page,
+    target_page: u32,
+    profile: &SelectorProfile,
+) -> Result<(), String> {
     use regex::Regex;
 
     if target_page <= 1 {
@@ -230,14 +221,16 @@ pub async fn navigate_to_page(page: &Page, target_page: u32) -> Result<(), Strin
             current_page + 1
         );
 
-        click_next_page_button(page).await?;
+        if !click_next_page_button(page, profile).await? {
+            return Err("Next page button is disabled".to_string());
+        }
 
         tokio::time::sleep(Duration::from_millis(
             super::config::PAGINATION_DELAY_MS,
         ))
         .await;
 
-        if let Ok(elements) = page.find_elements("div.paging-status").await {
+        if let Ok(elements) = page.find_elements(profile.paging_status.as_str()).await {
             if let Some(status_element) = elements.into_iter().next() {
                 if let Ok(Some(status_text)) = status_element.inner_text().await {
                     println!("Current page status: {}", status_text);
@@ -0,0 +1,229 @@
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::config::ScraperConfig;
+
+/// Environment variable that points at a browser executable
+pub const CHROME_PATH_ENV: &str = "CHROME_PATH";
+
+/// Browser executables below `Program Files` or `%LOCALAPPDATA%` on Windows
+const WINDOWS_BROWSER_PATHS: &[&str] = &[
+    "Google/Chrome/Application/chrome.exe",
+    "Chromium/Application/chrome.exe",
+    "Microsoft/Edge/Application/msedge.exe",
+    "BraveSoftware/Brave-Browser/Application/brave.exe",
+];
+
+/// Environment variables holding the Windows base folders browsers install into
+const WINDOWS_BASE_DIR_ENVS: &[&str] = &["ProgramFiles", "ProgramFiles(x86)", "LOCALAPPDATA"];
+
+/// Common browser executables on Linux
+const LINUX_BROWSER_PATHS: &[&str] = &[
+    "/usr/bin/google-chrome",
+    "/usr/bin/google-chrome-stable",
+    "/opt/google/chrome/chrome",
+    "/usr/bin/chromium",
+    "/usr/bin/chromium-browser",
+    "/snap/bin/chromium",
+    "/usr/bin/microsoft-edge",
+    "/usr/bin/microsoft-edge-stable",
+    "/opt/microsoft/msedge/msedge",
+    "/usr/bin/brave-browser",
+    "/opt/brave.com/brave/brave",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BrowserSource {
+    Configured,
+    Environment,
+    Detected,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BrowserDetection {
+    pub executable_path: Option<String>,
+    pub source: Option<BrowserSource>,
+    pub tried: Vec<String>,
+}
+
+/// Candidate executables for each Windows base folder, in `WINDOWS_BROWSER_PATHS` order
+pub fn windows_browser_candidates(base_dirs: &[PathBuf]) -> Vec<PathBuf> {
+    WINDOWS_BROWSER_PATHS
+        .iter()
+        .flat_map(|relative| base_dirs.iter().map(move |base| base.join(relative)))
+        .collect()
+}
+
+/// Well-known browser locations for the current platform
+fn platform_browser_candidates() -> Vec<PathBuf> {
+    if cfg!(target_os = "windows") {
+        let base_dirs: Vec<PathBuf> = WINDOWS_BASE_DIR_ENVS
+            .iter()
+            .filter_map(std::env::var_os)
+            .map(PathBuf::from)
+            .collect();
+        windows_browser_candidates(&base_dirs)
+    } else {
+        LINUX_BROWSER_PATHS.iter().map(PathBuf::from).collect()
+    }
+}
+
+/// Pick the first existing executable: configured path, then `CHROME_PATH`, then known locations
+pub fn detect_browser(
+    configured_path: Option<&str>,
+    env_path: Option<&str>,
+    candidates: &[PathBuf],
+) -> BrowserDetection {
+    let ordered = configured_path
+        .map(|path| (PathBuf::from(path), BrowserSource::Configured))
+        .into_iter()
+        .chain(env_path.map(|path| (PathBuf::from(path), BrowserSource::Environment)))
+        .chain(
+            candidates
+                .iter()
+                .map(|path| (path.clone(), BrowserSource::Detected)),
+        );
+
+    let mut tried = Vec::new();
+    for (path, source) in ordered {
+        tried.push(path.to_string_lossy().to_string());
+        if path.is_file() {
+            return BrowserDetection {
+                executable_path: Some(path.to_string_lossy().to_string()),
+                source: Some(source),
+                tried,
+            };
+        }
+    }
+
+    BrowserDetection {
+        executable_path: None,
+        source: None,
+        tried,
+    }
+}
+
+fn detect_browser_from_settings() -> BrowserDetection {
+    let config = ScraperConfig::load().unwrap_or_default();
+    let env_path = std::env::var(CHROME_PATH_ENV)
+        .ok()
+        .filter(|path| !path.trim().is_empty());
+
+    detect_browser(
+        config.browser_path.as_deref(),
+        env_path.as_deref(),
+        &platform_browser_candidates(),
+    )
+}
+
+/// Browser executable to launch, or an error listing every location tried
+pub fn resolve_browser_executable() -> Result<PathBuf, String> {
+    let detection = detect_browser_from_settings();
+
+    detection
+        .executable_path
+        .map(PathBuf::from)
+        .ok_or_else(|| {
+            format!(
+                "No Chromium-based browser found. Install Chrome, Edge, Chromium or Brave, or set a browser path in the settings or the {} environment variable. Tried:\n  {}",
+                CHROME_PATH_ENV,
+                detection.tried.join("\n  ")
+            )
+        })
+}
+
+#[tauri::command]
+pub fn detect_browser_executable() -> Result<BrowserDetection, String> {
+    Ok(detect_browser_from_settings())
+}
+
+/// Save the browser executable used by the scraper; `None` returns to auto-detection
+#[tauri::command]
+pub fn save_browser_path(browser_path: Option<String>) -> Result<BrowserDetection, String> {
+    let config = ScraperConfig::load()
+        .unwrap_or_default()
+        .with_browser_path(browser_path);
+
+    if let Some(path) = &config.browser_path {
+        if !Path::new(path).is_file() {
+            return Err(format!("Browser executable not found: {}", path));
+        }
+    }
+
+    config.save()?;
+    Ok(detect_browser_from_settings())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn fake_executable(dir: &Path, relative: &str) -> PathBuf {
+        let path = dir.join(relative);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, b"").unwrap();
+        path
+    }
+
+    #[test]
+    fn test_windows_browser_candidates() {
+        let candidates = windows_browser_candidates(&[
+            PathBuf::from("C:/Program Files"),
+            PathBuf::from("C:/Users/me/AppData/Local"),
+        ]);
+
+        assert_eq!(candidates.len(), WINDOWS_BROWSER_PATHS.len() * 2);
+        assert_eq!(
+            candidates[0],
+            Path::new("C:/Program Files").join("Google/Chrome/Application/chrome.exe")
+        );
+        assert_eq!(
+            candidates[1],
+            Path::new("C:/Users/me/AppData/Local").join("Google/Chrome/Application/chrome.exe")
+        );
+    }
+
+    #[test]
+    fn test_detect_browser_prefers_configured_then_env() {
+        let dir = tempfile::tempdir().unwrap();
+        let configured = fake_executable(dir.path(), "configured/chrome");
+        let env = fake_executable(dir.path(), "env/chrome");
+        let detected = fake_executable(dir.path(), "detected/msedge");
+        let candidates = vec![detected.clone()];
+
+        let detection = detect_browser(
+            configured.to_str(),
+            env.to_str(),
+            std::slice::from_ref(&detected),
+        );
+        assert_eq!(detection.source, Some(BrowserSource::Configured));
+
+        let detection = detect_browser(None, env.to_str(), &candidates);
+        assert_eq!(detection.source, Some(BrowserSource::Environment));
+
+        let missing = dir.path().join("missing/chrome");
+        let detection = detect_browser(missing.to_str(), None, &candidates);
+        assert_eq!(detection.source, Some(BrowserSource::Detected));
+        assert_eq!(detection.executable_path.as_deref(), detected.to_str());
+        assert_eq!(detection.tried.len(), 2);
+    }
+
+    #[test]
+    fn test_detect_browser_lists_tried_paths_when_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        let candidates = vec![dir.path().join("a/chrome"), dir.path().join("b/brave")];
+
+        let detection = detect_browser(None, None, &candidates);
+
+        assert_eq!(detection.executable_path, None);
+        assert_eq!(
+            detection.tried,
+            candidates
+                .iter()
+                .map(|path| path.to_string_lossy().to_string())
+                .collect::<Vec<_>>()
+        );
+    }
+}
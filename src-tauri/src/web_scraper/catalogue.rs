@@ -0,0 +1,169 @@
+use std::fs;
+use tauri::AppHandle;
+
+use crate::storage::{
+    load_downloadable_versions_catalogue, merge_and_save_imported_catalogue,
+    parse_catalogue_from_value,
+};
+
+use super::config::DownloadableVersionsCache;
+use super::freshness::notify_new_versions;
+use super::network::{artifacts_base_url, load_network_settings};
+
+fn is_dotted_number(value: &str) -> bool {
+    value.split('.').count() >= 2
+        && value
+            .split('.')
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Parse a catalogue file, rejecting malformed versions and build numbers
+pub fn parse_catalogue(content: &str) -> Result<DownloadableVersionsCache, String> {
+    let value: serde_json::Value = serde_json::from_str(content)
+        .map_err(|e| format!("Failed to parse catalogue file: {}", e))?;
+    let catalogue = parse_catalogue_from_value(&value)?;
+
+    for version in &catalogue.versions {
+        if !is_dotted_number(&version.version) {
+            return Err(format!("Invalid version in catalogue: {}", version.version));
+        }
+        if let Some(build_number) = version.build_number.as_deref() {
+            if build_number.is_empty() || !build_number.chars().all(|c| c.is_ascii_digit()) {
+                return Err(format!(
+                    "Invalid build number for {} in catalogue: {}",
+                    version.version, build_number
+                ));
+            }
+        }
+    }
+
+    Ok(catalogue)
+}
+
+/// Point the installer URLs of an imported catalogue at this machine's artifacts mirror
+pub fn rebase_catalogue(
+    catalogue: DownloadableVersionsCache,
+    artifacts_base_url: &str,
+) -> DownloadableVersionsCache {
+    DownloadableVersionsCache {
+        versions: catalogue
+            .versions
+            .into_iter()
+            .map(|version| version.with_platform_artifacts(artifacts_base_url))
+            .collect(),
+        ..catalogue
+    }
+}
+
+/// Write the downloadable versions cache, with build numbers and installer URLs, to `path`
+#[tauri::command]
+pub fn export_downloadable_versions_catalogue(path: String) -> Result<usize, String> {
+    let catalogue = load_downloadable_versions_catalogue();
+    if catalogue.versions.is_empty() {
+        return Err("No downloadable versions to export".to_string());
+    }
+
+    let content = serde_json::to_string_pretty(&catalogue)
+        .map_err(|e| format!("Failed to serialize catalogue: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write catalogue file: {}", e))?;
+
+    println!(
+        "[Catalogue] Exported {} versions to {}",
+        catalogue.versions.len(),
+        path
+    );
+    Ok(catalogue.versions.len())
+}
+
+/// Merge a catalogue file exported on another machine into the downloadable versions cache
+#[tauri::command]
+pub fn import_downloadable_versions_catalogue(
+    app_handle: AppHandle,
+    path: String,
+) -> Result<DownloadableVersionsCache, String> {
+    let content =
+        fs::read_to_string(&path).map_err(|e| format!("Failed to read catalogue file: {}", e))?;
+    let network = load_network_settings();
+    let catalogue = rebase_catalogue(parse_catalogue(&content)?, artifacts_base_url(&network));
+
+    let previous = load_downloadable_versions_catalogue().versions;
+    let cache = merge_and_save_imported_catalogue(catalogue, &path)?;
+    println!(
+        "[Catalogue] Imported {} versions from {}",
+        cache
+            .last_import
+            .as_ref()
+            .map_or(0, |import| import.versions_imported),
+        path
+    );
+
+    notify_new_versions(&app_handle, &previous, &cache.versions);
+    Ok(cache)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::web_scraper::config::ArtifactPlatform;
+
+    const MIRROR: &str = "https://mirror.example.com/modelers";
+
+    #[test]
+    fn test_parse_and_rebase_catalogue() {
+        let content = r#"{
+            "versions": [
+                {
+                    "version": "10.24.0",
+                    "download_url": "",
+                    "release_date": null,
+                    "release_notes_url": null,
+                    "file_size": null,
+                    "is_lts": true,
+                    "is_beta": false,
+                    "is_mts": false,
+                    "is_latest": false,
+                    "build_number": "77000",
+                    "artifacts": [
+                        {
+                            "platform": "windows",
+                            "download_url": "https://artifacts.rnd.mendix.com/modelers/Mendix-10.24.0.77000-Setup.exe"
+                        }
+                    ]
+                }
+            ],
+            "last_refreshed_at": "2026-10-01T08:00:00Z"
+        }"#;
+
+        let catalogue = rebase_catalogue(parse_catalogue(content).unwrap(), MIRROR);
+
+        assert!(catalogue.last_refreshed_at.is_some());
+        assert_eq!(catalogue.last_import, None);
+        let version = &catalogue.versions[0];
+        assert_eq!(version.build_number.as_deref(), Some("77000"));
+        assert_eq!(version.artifacts.len(), 3);
+        assert_eq!(version.artifacts[0].platform, ArtifactPlatform::Windows);
+        assert_eq!(
+            version.artifacts[0].download_url.as_deref(),
+            Some("https://mirror.example.com/modelers/Mendix-10.24.0.77000-Setup.exe")
+        );
+    }
+
+    #[test]
+    fn test_parse_catalogue_rejects_invalid_entries() {
+        let entry = |version: &str, build_number: &str| {
+            format!(
+                r#"[{{ "version": "{}", "download_url": "", "release_date": null,
+                "release_notes_url": null, "file_size": null, "is_lts": false,
+                "is_beta": false, "is_mts": false, "is_latest": false,
+                "build_number": "{}" }}]"#,
+                version, build_number
+            )
+        };
+
+        assert!(parse_catalogue(&entry("10.24.0", "77000")).is_ok());
+        assert!(parse_catalogue(&entry("10.24.x", "77000")).is_err());
+        assert!(parse_catalogue(&entry("10", "77000")).is_err());
+        assert!(parse_catalogue(&entry("10.24.0", "77a00")).is_err());
+        assert!(parse_catalogue("not json").is_err());
+    }
+}
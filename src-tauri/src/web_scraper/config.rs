@@ -1,8 +1,6 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-/// Chrome executable path on Windows
-pub const CHROME_EXECUTABLE_PATH: &str = "C:/Program Files/Google/Chrome/Application/chrome.exe";
-
 /// Default timeout for navigation in seconds
 pub const NAVIGATION_TIMEOUT_SECS: u64 = 60;
 
@@ -27,12 +25,18 @@ pub const BUILD_NUMBER_EXTRACT_DELAY_MS: u64 = 3000;
 /// Page click navigation delay in milliseconds
 pub const CLICK_NAVIGATION_DELAY_MS: u64 = 2000;
 
-/// Mendix marketplace base URL
+/// Default Mendix marketplace base URL
 pub const MENDIX_MARKETPLACE_URL: &str = "https://marketplace.mendix.com/link/studiopro";
 
-/// Mendix artifacts download base URL
+/// Default Mendix artifacts download base URL
 pub const MENDIX_ARTIFACTS_BASE_URL: &str = "https://artifacts.rnd.mendix.com/modelers";
 
+/// Mendix Studio Pro release notes base URL
+pub const MENDIX_RELEASE_NOTES_BASE_URL: &str = "https://docs.mendix.com/releasenotes/studio-pro";
+
+/// First major version with macOS installers
+const MACOS_MIN_MAJOR: u32 = 10;
+
 /// Scraping configuration with timeout settings
 #[derive(Debug, Clone)]
 pub struct ScrapingConfig {
@@ -87,6 +91,13 @@ pub struct DownloadableVersion {
     pub is_beta: bool,
     pub is_mts: bool,
     pub is_latest: bool,
+    #[serde(default)]
+    pub first_seen_at: Option<DateTime<Utc>>,
+    /// Marketplace build number, needed for installer URLs below 11
+    #[serde(default)]
+    pub build_number: Option<String>,
+    #[serde(default)]
+    pub artifacts: Vec<PlatformArtifact>,
 }
 
 impl DownloadableVersion {
@@ -108,10 +119,91 @@ impl DownloadableVersion {
             is_beta: flags.is_beta,
             is_mts: flags.is_mts,
             is_latest: flags.is_latest,
+            first_seen_at: None,
+            build_number: None,
+            artifacts: Vec::new(),
+        }
+    }
+
+    /// Fill in the installers published for this version on every platform
+    pub fn with_platform_artifacts(self, artifacts_base_url: &str) -> Self {
+        let artifacts = construct_platform_artifacts(
+            artifacts_base_url,
+            &self.version,
+            self.build_number.as_deref(),
+        );
+        DownloadableVersion { artifacts, ..self }
+    }
+
+    pub fn with_build_number(self, build_number: Option<String>) -> Self {
+        DownloadableVersion {
+            build_number,
+            ..self
+        }
+    }
+}
+
+/// Operating system and architecture a Studio Pro installer is built for
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+pub enum ArtifactPlatform {
+    Windows,
+    MacosArm64,
+    MacosX64,
+}
+
+impl ArtifactPlatform {
+    pub const ALL: [ArtifactPlatform; 3] = [
+        ArtifactPlatform::Windows,
+        ArtifactPlatform::MacosArm64,
+        ArtifactPlatform::MacosX64,
+    ];
+
+    /// Platform of the running machine, if Studio Pro is published for it
+    pub fn current() -> Option<Self> {
+        match (std::env::consts::OS, std::env::consts::ARCH) {
+            ("windows", _) => Some(ArtifactPlatform::Windows),
+            ("macos", "aarch64") => Some(ArtifactPlatform::MacosArm64),
+            ("macos", "x86_64") => Some(ArtifactPlatform::MacosX64),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ArtifactPlatform::Windows => "Windows",
+            ArtifactPlatform::MacosArm64 => "macOS (Apple silicon)",
+            ArtifactPlatform::MacosX64 => "macOS (Intel)",
+        }
+    }
+
+    pub fn file_suffix(self) -> &'static str {
+        match self {
+            ArtifactPlatform::Windows => "Setup.exe",
+            ArtifactPlatform::MacosArm64 => "macos-arm64.dmg",
+            ArtifactPlatform::MacosX64 => "macos-x64.dmg",
+        }
+    }
+
+    /// Whether an installer is published for this platform; macOS builds start with 10.0
+    pub fn is_available_for(self, version: &str) -> bool {
+        match self {
+            ArtifactPlatform::Windows => true,
+            ArtifactPlatform::MacosArm64 | ArtifactPlatform::MacosX64 => {
+                parse_version_parts(version).first().copied().unwrap_or(0) >= MACOS_MIN_MAJOR
+            }
         }
     }
 }
 
+/// Installer of a version for one platform
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PlatformArtifact {
+    pub platform: ArtifactPlatform,
+    /// `None` below 11, where the URL contains a build number read from the marketplace
+    pub download_url: Option<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BuildInfo {
     pub build_number: String,
@@ -121,31 +213,132 @@ pub struct BuildInfo {
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct DownloadableVersionsCache {
     pub versions: Vec<DownloadableVersion>,
+    #[serde(default)]
+    pub last_refreshed_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub last_import: Option<CatalogueImport>,
+}
+
+/// Where the last imported catalogue file came from and when it was imported
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CatalogueImport {
+    pub source: String,
+    pub imported_at: DateTime<Utc>,
+    pub versions_imported: usize,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DownloadProgress {
+    pub version: String,
     pub downloaded_bytes: u64,
     pub total_bytes: Option<u64>,
     pub percentage: Option<f32>,
 }
 
+impl DownloadProgress {
+    pub fn new(version: &str, downloaded_bytes: u64, total_bytes: Option<u64>) -> Self {
+        Self {
+            version: version.to_string(),
+            downloaded_bytes,
+            total_bytes,
+            percentage: total_bytes
+                .filter(|total| *total > 0)
+                .map(|total| (downloaded_bytes as f64 / total as f64 * 100.0) as f32),
+        }
+    }
+}
+
 /// Construct the marketplace URL for a specific version
-pub fn construct_marketplace_url(version: &str) -> String {
-    format!("{}/{}", MENDIX_MARKETPLACE_URL, version)
+pub fn construct_marketplace_url(marketplace_url: &str, version: &str) -> String {
+    format!("{}/{}", marketplace_url, version)
+}
+
+/// Installer file name of a platform: versions below 11 carry the build number,
+/// e.g. `Mendix-10.24.0.12345-Setup.exe`, 11+ do not, e.g. `Mendix-11.0.0-macos-arm64.dmg`
+pub fn construct_artifact_file_name(
+    version: &str,
+    build_number: Option<&str>,
+    platform: ArtifactPlatform,
+) -> Option<String> {
+    if !platform.is_available_for(version) {
+        return None;
+    }
+
+    let versioned = match build_number {
+        Some(build_number) if !is_version_11_or_above(version) => {
+            format!("{}.{}", version, build_number)
+        }
+        None if !is_version_11_or_above(version) => return None,
+        _ => version.to_string(),
+    };
+    Some(format!("Mendix-{}-{}", versioned, platform.file_suffix()))
+}
+
+/// Construct the download URL of a platform installer; below 11 it needs the build number
+pub fn construct_download_url(
+    artifacts_base_url: &str,
+    version: &str,
+    build_number: Option<&str>,
+    platform: ArtifactPlatform,
+) -> Option<String> {
+    construct_artifact_file_name(version, build_number, platform)
+        .map(|file_name| format!("{}/{}", artifacts_base_url, file_name))
+}
+
+/// Installers published for a version, with URLs where the build number is known or not needed
+pub fn construct_platform_artifacts(
+    artifacts_base_url: &str,
+    version: &str,
+    build_number: Option<&str>,
+) -> Vec<PlatformArtifact> {
+    ArtifactPlatform::ALL
+        .into_iter()
+        .filter(|platform| platform.is_available_for(version))
+        .map(|platform| PlatformArtifact {
+            platform,
+            download_url: construct_download_url(
+                artifacts_base_url,
+                version,
+                build_number,
+                platform,
+            ),
+        })
+        .collect()
+}
+
+/// `10.4.1` -> `(10, 4)`
+pub fn split_major_minor(version: &str) -> Option<(u64, u64)> {
+    let mut parts = version.trim().split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next()?.parse().ok()?;
+    Some((major, minor))
+}
+
+/// Construct the release notes page URL of a minor version
+pub fn construct_release_notes_page_url(major: u64, minor: u64) -> String {
+    format!("{}/{}.{}/", MENDIX_RELEASE_NOTES_BASE_URL, major, minor)
 }
 
-/// Construct download URL for versions below 11
-pub fn construct_download_url(version: &str, build_number: &str) -> String {
-    format!(
-        "{}/Mendix-{}.{}-Setup.exe",
-        MENDIX_ARTIFACTS_BASE_URL, version, build_number
-    )
+/// Construct the release notes URL of a version, anchored at its section
+pub fn construct_release_notes_url(version: &str) -> Option<String> {
+    let (major, minor) = split_major_minor(version)?;
+    let anchor: String = version
+        .trim()
+        .split('.')
+        .take(3)
+        .collect::<Vec<_>>()
+        .concat();
+
+    Some(format!(
+        "{}#{}",
+        construct_release_notes_page_url(major, minor),
+        anchor
+    ))
 }
 
-/// Construct download URL for version 11+
-pub fn construct_download_url_v11(version: &str) -> String {
-    format!("{}/Mendix-{}-Setup.exe", MENDIX_ARTIFACTS_BASE_URL, version)
+/// Split a dotted version into numeric parts for ordering
+pub fn parse_version_parts(version: &str) -> Vec<u32> {
+    version.split('.').map(|s| s.parse().unwrap_or(0)).collect()
 }
 
 /// Check if version is 11 or above
@@ -157,3 +350,121 @@ pub fn is_version_11_or_above(version: &str) -> bool {
         .map(|major| major >= 11)
         .unwrap_or(false)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BASE: &str = "https://artifacts.example.com/modelers";
+
+    #[test]
+    fn test_construct_download_url_per_version_range() {
+        let cases = [
+            (
+                "9.24.0",
+                Some("12345"),
+                ArtifactPlatform::Windows,
+                Some("Mendix-9.24.0.12345-Setup.exe"),
+            ),
+            ("9.24.0", Some("12345"), ArtifactPlatform::MacosArm64, None),
+            (
+                "10.24.0",
+                Some("77000"),
+                ArtifactPlatform::Windows,
+                Some("Mendix-10.24.0.77000-Setup.exe"),
+            ),
+            (
+                "10.24.0",
+                Some("77000"),
+                ArtifactPlatform::MacosArm64,
+                Some("Mendix-10.24.0.77000-macos-arm64.dmg"),
+            ),
+            (
+                "10.24.0",
+                Some("77000"),
+                ArtifactPlatform::MacosX64,
+                Some("Mendix-10.24.0.77000-macos-x64.dmg"),
+            ),
+            ("10.24.0", None, ArtifactPlatform::Windows, None),
+            (
+                "11.0.0",
+                None,
+                ArtifactPlatform::Windows,
+                Some("Mendix-11.0.0-Setup.exe"),
+            ),
+            (
+                "11.0.0",
+                Some("80000"),
+                ArtifactPlatform::MacosArm64,
+                Some("Mendix-11.0.0-macos-arm64.dmg"),
+            ),
+            (
+                "11.2.0",
+                None,
+                ArtifactPlatform::MacosX64,
+                Some("Mendix-11.2.0-macos-x64.dmg"),
+            ),
+        ];
+
+        for (version, build_number, platform, expected) in cases {
+            assert_eq!(
+                construct_download_url(BASE, version, build_number, platform),
+                expected.map(|file_name| format!("{}/{}", BASE, file_name)),
+                "{} {:?}",
+                version,
+                platform
+            );
+        }
+    }
+
+    #[test]
+    fn test_with_platform_artifacts() {
+        let version = |version: &str| {
+            DownloadableVersion::new(
+                version.to_string(),
+                String::new(),
+                None,
+                None,
+                None,
+                VersionFlags::default(),
+            )
+            .with_platform_artifacts(BASE)
+        };
+
+        let legacy = version("9.24.0");
+        assert_eq!(
+            legacy.artifacts,
+            vec![PlatformArtifact {
+                platform: ArtifactPlatform::Windows,
+                download_url: None,
+            }]
+        );
+
+        let platforms: Vec<_> = version("10.24.0")
+            .artifacts
+            .iter()
+            .map(|artifact| (artifact.platform, artifact.download_url.is_some()))
+            .collect();
+        assert_eq!(
+            platforms,
+            vec![
+                (ArtifactPlatform::Windows, false),
+                (ArtifactPlatform::MacosArm64, false),
+                (ArtifactPlatform::MacosX64, false),
+            ]
+        );
+
+        let with_build = version("10.24.0")
+            .with_build_number(Some("77000".to_string()))
+            .with_platform_artifacts(BASE);
+        assert!(with_build
+            .artifacts
+            .iter()
+            .all(|artifact| artifact.download_url.is_some()));
+
+        assert!(version("11.0.0")
+            .artifacts
+            .iter()
+            .all(|artifact| artifact.download_url.is_some()));
+    }
+}
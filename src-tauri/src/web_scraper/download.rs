@@ -1,58 +1,382 @@
 use futures::TryStreamExt;
+use reqwest::header::{CONTENT_RANGE, RANGE};
+use reqwest::StatusCode;
+use std::collections::HashMap;
+use std::path::Path;
 use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
 use tokio::io::AsyncWriteExt;
+use tokio::sync::Notify;
 
-/// Download a file from URL to the specified path
-pub async fn download_file_to_path(url: &str, file_path: &str) -> Result<(), String> {
-    let client = reqwest::Client::builder()
-        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36")
+use crate::config::NetworkSettings;
+
+use super::network::configure_http_client;
+
+/// Suffix of the partially downloaded file that later attempts resume from
+const PARTIAL_DOWNLOAD_SUFFIX: &str = ".part";
+
+/// Minimum time between two progress reports in milliseconds
+const PROGRESS_INTERVAL_MS: u64 = 250;
+
+/// Reconnect attempts after the connection drops mid-download
+const MAX_RESUME_ATTEMPTS: u32 = 3;
+
+/// Delay before reconnecting in milliseconds
+const RESUME_DELAY_MS: u64 = 500;
+
+/// Time to wait for the next chunk before treating the connection as stalled, in seconds
+const CHUNK_READ_TIMEOUT_SECS: u64 = 30;
+
+/// Cancellation request of a download; waking the waiter lets a read that is stuck
+/// on the network end right away
+#[derive(Default)]
+pub struct CancelSignal {
+    cancelled: AtomicBool,
+    notify: Notify,
+}
+
+impl CancelSignal {
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        self.notify.notify_waiters();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Resolve once the download is cancelled
+    pub async fn wait(&self) {
+        let notified = self.notify.notified();
+        tokio::pin!(notified);
+        notified.as_mut().enable();
+
+        if !self.is_cancelled() {
+            notified.await;
+        }
+    }
+}
+
+fn active_downloads() -> &'static Mutex<HashMap<String, Arc<CancelSignal>>> {
+    static DOWNLOADS: OnceLock<Mutex<HashMap<String, Arc<CancelSignal>>>> = OnceLock::new();
+    DOWNLOADS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Cancellation signal of a running download; unregisters the download when dropped
+pub struct DownloadGuard {
+    version: String,
+    cancel: Arc<CancelSignal>,
+}
+
+impl DownloadGuard {
+    pub fn cancel_signal(&self) -> &CancelSignal {
+        &self.cancel
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.is_cancelled()
+    }
+}
+
+impl Drop for DownloadGuard {
+    fn drop(&mut self) {
+        if let Ok(mut downloads) = active_downloads().lock() {
+            downloads.remove(&self.version);
+        }
+    }
+}
+
+/// Register a download so it can be cancelled by version
+pub fn register_download(version: &str) -> Result<DownloadGuard, String> {
+    let mut downloads = active_downloads()
+        .lock()
+        .map_err(|e| format!("Failed to lock download registry: {}", e))?;
+
+    if downloads.contains_key(version) {
+        return Err(format!("Mendix {} is already being downloaded", version));
+    }
+
+    let cancel = Arc::new(CancelSignal::default());
+    downloads.insert(version.to_string(), cancel.clone());
+
+    Ok(DownloadGuard {
+        version: version.to_string(),
+        cancel,
+    })
+}
+
+/// Request cancellation of a running download; returns whether one was found
+pub fn cancel_download(version: &str) -> bool {
+    active_downloads()
+        .lock()
+        .ok()
+        .and_then(|downloads| downloads.get(version).cloned())
+        .map(|cancel| cancel.cancel())
+        .is_some()
+}
+
+/// Path of the partial file kept next to the target while downloading
+pub fn partial_download_path(file_path: &str) -> String {
+    format!("{}{}", file_path, PARTIAL_DOWNLOAD_SUFFIX)
+}
+
+enum AttemptError {
+    /// The connection failed or dropped; another attempt can resume
+    Interrupted(String),
+    Fatal(String),
+}
+
+fn create_download_client(network: &NetworkSettings) -> Result<reqwest::Client, String> {
+    let builder = reqwest::Client::builder()
+        .user_agent("Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/120.0.0.0 Safari/537.36");
+
+    configure_http_client(builder, network)?
         .build()
-        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))
+}
+
+/// `bytes 100-199/1000` or `bytes */1000` -> `1000`
+fn parse_content_range_total(response: &reqwest::Response) -> Option<u64> {
+    response
+        .headers()
+        .get(CONTENT_RANGE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.rsplit('/').next())
+        .and_then(|total| total.trim().parse().ok())
+}
 
-    let response = client
+async fn partial_file_size(part_path: &str) -> u64 {
+    tokio::fs::metadata(part_path)
+        .await
+        .map(|metadata| metadata.len())
+        .unwrap_or(0)
+}
+
+async fn download_attempt<F>(
+    client: &reqwest::Client,
+    url: &str,
+    part_path: &str,
+    cancel: &CancelSignal,
+    on_progress: &mut F,
+) -> Result<(), AttemptError>
+where
+    F: FnMut(u64, Option<u64>),
+{
+    let existing_bytes = partial_file_size(part_path).await;
+
+    let mut request = client
         .get(url)
         .header("Referer", "https://marketplace.mendix.com/")
-        .header("Accept", "application/octet-stream,*/*")
-        .send()
-        .await
-        .map_err(|e| format!("Failed to start download: {}", e))?;
+        .header("Accept", "application/octet-stream,*/*");
+    if existing_bytes > 0 {
+        request = request.header(RANGE, format!("bytes={}-", existing_bytes));
+    }
+
+    let response = tokio::select! {
+        response = request.send() => response
+            .map_err(|e| AttemptError::Interrupted(format!("Failed to start download: {}", e)))?,
+        _ = cancel.wait() => {
+            return Err(AttemptError::Fatal("Download cancelled".to_string()));
+        }
+    };
+
+    if response.status() == StatusCode::RANGE_NOT_SATISFIABLE && existing_bytes > 0 {
+        if parse_content_range_total(&response) == Some(existing_bytes) {
+            on_progress(existing_bytes, Some(existing_bytes));
+            return Ok(());
+        }
+        let _ = tokio::fs::remove_file(part_path).await;
+        return Err(AttemptError::Interrupted(
+            "Partial download does not match the server file".to_string(),
+        ));
+    }
 
     if !response.status().is_success() {
-        return Err(format!(
+        return Err(AttemptError::Fatal(format!(
             "Download failed with status: {}",
             response.status()
-        ));
+        )));
     }
 
-    let mut file = tokio::fs::File::create(file_path)
+    let resumed = response.status() == StatusCode::PARTIAL_CONTENT;
+    let offset = if resumed { existing_bytes } else { 0 };
+    let total_bytes = if resumed {
+        parse_content_range_total(&response)
+    } else {
+        response.content_length()
+    };
+
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(resumed)
+        .truncate(!resumed)
+        .open(part_path)
         .await
-        .map_err(|e| format!("Failed to create file: {}", e))?;
+        .map_err(|e| AttemptError::Fatal(format!("Failed to create file: {}", e)))?;
+
+    if resumed {
+        println!("[Download] Resuming from {} bytes", offset);
+    }
 
     let mut stream = response.bytes_stream();
-    let mut total_bytes = 0;
+    let mut downloaded_bytes = offset;
+    let mut last_report: Option<Instant> = None;
+    let report_interval = Duration::from_millis(PROGRESS_INTERVAL_MS);
+    let read_timeout = Duration::from_secs(CHUNK_READ_TIMEOUT_SECS);
+
+    let result = async {
+        loop {
+            let next_chunk = tokio::select! {
+                next_chunk = tokio::time::timeout(read_timeout, stream.try_next()) => next_chunk,
+                _ = cancel.wait() => {
+                    return Err(AttemptError::Fatal("Download cancelled".to_string()));
+                }
+            };
+            let chunk = match next_chunk {
+                Ok(Ok(Some(chunk))) => chunk,
+                Ok(Ok(None)) => break,
+                Ok(Err(e)) => {
+                    return Err(AttemptError::Interrupted(format!(
+                        "Failed to read chunk: {}",
+                        e
+                    )))
+                }
+                Err(_) => {
+                    return Err(AttemptError::Interrupted(format!(
+                        "No data received for {} seconds",
+                        CHUNK_READ_TIMEOUT_SECS
+                    )))
+                }
+            };
+
+            file.write_all(&chunk)
+                .await
+                .map_err(|e| AttemptError::Fatal(format!("Failed to write chunk: {}", e)))?;
+            downloaded_bytes += chunk.len() as u64;
+
+            if last_report.is_none_or(|reported| reported.elapsed() >= report_interval) {
+                on_progress(downloaded_bytes, total_bytes);
+                last_report = Some(Instant::now());
+            }
+        }
+        Ok(())
+    }
+    .await;
 
-    while let Some(chunk) = stream
-        .try_next()
+    file.flush()
         .await
-        .map_err(|e| format!("Failed to read chunk: {}", e))?
-    {
-        total_bytes += chunk.len();
+        .map_err(|e| AttemptError::Fatal(format!("Failed to flush file: {}", e)))?;
+    result?;
 
-        file.write_all(&chunk)
-            .await
-            .map_err(|e| format!("Failed to write chunk: {}", e))?;
+    if total_bytes.is_some_and(|total| downloaded_bytes < total) {
+        return Err(AttemptError::Interrupted(
+            "Connection closed before the download completed".to_string(),
+        ));
     }
 
-    println!("✅ Download completed: {} MB", total_bytes / (1024 * 1024));
+    on_progress(downloaded_bytes, total_bytes);
+    Ok(())
+}
 
-    file.flush()
+/// Download a file from URL to the specified path.
+///
+/// Data is written to a `.part` file first so an interrupted or cancelled download
+/// resumes with an HTTP Range request instead of starting over.
+pub async fn download_file_to_path<F>(
+    network: &NetworkSettings,
+    url: &str,
+    file_path: &str,
+    cancel: &CancelSignal,
+    mut on_progress: F,
+) -> Result<(), String>
+where
+    F: FnMut(u64, Option<u64>),
+{
+    let client = create_download_client(network)?;
+    let part_path = partial_download_path(file_path);
+    let mut attempt = 0;
+
+    loop {
+        match download_attempt(&client, url, &part_path, cancel, &mut on_progress).await {
+            Ok(()) => break,
+            Err(AttemptError::Interrupted(message)) if attempt < MAX_RESUME_ATTEMPTS => {
+                attempt += 1;
+                println!(
+                    "[Download] {}, resuming (attempt {}/{})",
+                    message, attempt, MAX_RESUME_ATTEMPTS
+                );
+                tokio::time::sleep(Duration::from_millis(RESUME_DELAY_MS)).await;
+            }
+            Err(AttemptError::Interrupted(message)) | Err(AttemptError::Fatal(message)) => {
+                return Err(message)
+            }
+        }
+    }
+
+    tokio::fs::rename(&part_path, file_path)
         .await
-        .map_err(|e| format!("Failed to flush file: {}", e))?;
+        .map_err(|e| format!("Failed to finalize download: {}", e))?;
+
+    let total_bytes = partial_file_size(file_path).await;
+    println!("✅ Download completed: {} MB", total_bytes / (1024 * 1024));
 
     Ok(())
 }
 
-/// Execute the installer executable
+/// Inno Setup arguments for an unattended install, into `target_dir` when given
+pub fn installer_args(target_dir: Option<&str>) -> Vec<String> {
+    ["/SILENT", "/SUPPRESSMSGBOXES", "/NORESTART"]
+        .into_iter()
+        .map(String::from)
+        .chain(target_dir.map(|dir| format!("/DIR={}", dir)))
+        .collect()
+}
+
+/// How the installer process ended; `exit_code` is `None` when it timed out or was killed
+#[derive(Debug, Clone, PartialEq)]
+pub struct InstallerExit {
+    pub exit_code: Option<i32>,
+    pub timed_out: bool,
+}
+
+/// Run the installer and wait for it to exit; on timeout it is left running
+pub async fn run_installer_and_wait(
+    installer_path: &Path,
+    args: &[String],
+    timeout: Duration,
+) -> Result<InstallerExit, String> {
+    let mut command = Command::new(installer_path);
+    command.args(args);
+
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        command.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let mut child = tokio::process::Command::from(command)
+        .spawn()
+        .map_err(|e| format!("Failed to execute installer: {}", e))?;
+
+    match tokio::time::timeout(timeout, child.wait()).await {
+        Ok(status) => Ok(InstallerExit {
+            exit_code: status
+                .map_err(|e| format!("Failed to wait for installer: {}", e))?
+                .code(),
+            timed_out: false,
+        }),
+        Err(_) => Ok(InstallerExit {
+            exit_code: None,
+            timed_out: true,
+        }),
+    }
+}
+
+/// Execute the installer executable; on macOS the disk image is opened
 pub fn execute_installer(installer_path: &str) -> Result<(), String> {
     #[cfg(target_os = "windows")]
     {
@@ -66,7 +390,15 @@ pub fn execute_installer(installer_path: &str) -> Result<(), String> {
             .map_err(|e| format!("Failed to execute installer: {}", e))?;
     }
 
-    #[cfg(not(target_os = "windows"))]
+    #[cfg(target_os = "macos")]
+    {
+        Command::new("open")
+            .arg(installer_path)
+            .spawn()
+            .map_err(|e| format!("Failed to open installer: {}", e))?;
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
     {
         Command::new(installer_path)
             .spawn()
@@ -75,3 +407,241 @@ pub fn execute_installer(installer_path: &str) -> Result<(), String> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::AsyncReadExt;
+    use tokio::net::{TcpListener, TcpStream};
+
+    fn test_body(len: usize) -> Vec<u8> {
+        (0..len).map(|i| (i % 251) as u8).collect()
+    }
+
+    fn parse_range_start(request: &str) -> Option<u64> {
+        request
+            .lines()
+            .find(|line| line.to_lowercase().starts_with("range:"))
+            .and_then(|line| line.split('=').nth(1))
+            .and_then(|range| range.trim_end_matches('-').trim().parse().ok())
+    }
+
+    async fn read_request(stream: &mut TcpStream) -> String {
+        let mut request = Vec::new();
+        let mut buffer = [0u8; 1024];
+        while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+            let read = stream.read(&mut buffer).await.unwrap();
+            if read == 0 {
+                break;
+            }
+            request.extend_from_slice(&buffer[..read]);
+        }
+        String::from_utf8_lossy(&request).to_string()
+    }
+
+    /// Range-aware stand-in for the artifacts server; the first response can be cut short
+    async fn spawn_server(
+        body: Vec<u8>,
+        drop_first_after: Option<usize>,
+    ) -> (String, Arc<Mutex<Vec<Option<u64>>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!(
+            "http://{}/Mendix-10.4.0-Setup.exe",
+            listener.local_addr().unwrap()
+        );
+        let ranges = Arc::new(Mutex::new(Vec::new()));
+        let recorded = ranges.clone();
+
+        tokio::spawn(async move {
+            let mut drop_after = drop_first_after;
+            loop {
+                let (mut stream, _) = listener.accept().await.unwrap();
+                let range_start = parse_range_start(&read_request(&mut stream).await);
+                recorded.lock().unwrap().push(range_start);
+
+                let start = range_start.unwrap_or(0) as usize;
+                let header = match range_start {
+                    Some(_) => format!(
+                        "HTTP/1.1 206 Partial Content\r\nContent-Length: {}\r\nContent-Range: bytes {}-{}/{}\r\nConnection: close\r\n\r\n",
+                        body.len() - start,
+                        start,
+                        body.len() - 1,
+                        body.len()
+                    ),
+                    None => format!(
+                        "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                        body.len()
+                    ),
+                };
+                let end = drop_after.take().map_or(body.len(), |limit| start + limit);
+
+                let _ = stream.write_all(header.as_bytes()).await;
+                for piece in body[start..end].chunks(16 * 1024) {
+                    if stream.write_all(piece).await.is_err() {
+                        break;
+                    }
+                }
+                let _ = stream.shutdown().await;
+            }
+        });
+
+        (url, ranges)
+    }
+
+    #[tokio::test]
+    async fn test_download_resumes_from_partial_file() {
+        let body = test_body(64 * 1024);
+        let (url, ranges) = spawn_server(body.clone(), None).await;
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("installer.exe");
+        let target = target.to_str().unwrap();
+        std::fs::write(partial_download_path(target), &body[..1000]).unwrap();
+
+        let network = NetworkSettings::default();
+        let mut last_progress = None;
+        download_file_to_path(
+            &network,
+            &url,
+            target,
+            &CancelSignal::default(),
+            |done, total| last_progress = Some((done, total)),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(std::fs::read(target).unwrap(), body);
+        assert!(!std::path::Path::new(&partial_download_path(target)).exists());
+        assert_eq!(*ranges.lock().unwrap(), vec![Some(1000)]);
+        assert_eq!(
+            last_progress,
+            Some((body.len() as u64, Some(body.len() as u64)))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_download_resumes_after_dropped_connection() {
+        let body = test_body(64 * 1024);
+        let (url, ranges) = spawn_server(body.clone(), Some(3000)).await;
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("installer.exe");
+        let target = target.to_str().unwrap();
+        let network = NetworkSettings::default();
+
+        download_file_to_path(&network, &url, target, &CancelSignal::default(), |_, _| {})
+            .await
+            .unwrap();
+
+        assert_eq!(std::fs::read(target).unwrap(), body);
+        assert_eq!(*ranges.lock().unwrap(), vec![None, Some(3000)]);
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_download_keeps_partial_file() {
+        let body = test_body(4 * 1024 * 1024);
+        let (url, _) = spawn_server(body.clone(), None).await;
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("installer.exe");
+        let target = target.to_str().unwrap();
+        let cancel = CancelSignal::default();
+        let network = NetworkSettings::default();
+
+        let result =
+            download_file_to_path(&network, &url, target, &cancel, |_, _| cancel.cancel()).await;
+
+        assert_eq!(result, Err("Download cancelled".to_string()));
+        assert!(!std::path::Path::new(target).exists());
+        let partial = std::fs::metadata(partial_download_path(target)).unwrap();
+        assert!(partial.len() > 0 && partial.len() < body.len() as u64);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_interrupts_stalled_download() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/installer.exe", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            read_request(&mut stream).await;
+            let _ = stream
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 1000\r\n\r\n0123456789")
+                .await;
+            tokio::time::sleep(Duration::from_secs(60)).await;
+        });
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("installer.exe");
+        let target = target.to_str().unwrap();
+        let cancel = Arc::new(CancelSignal::default());
+        let network = NetworkSettings::default();
+
+        let canceller = cancel.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            canceller.cancel();
+        });
+
+        let started_at = Instant::now();
+        let result = download_file_to_path(&network, &url, target, &cancel, |_, _| {}).await;
+
+        assert_eq!(result, Err("Download cancelled".to_string()));
+        assert!(started_at.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_installer_args() {
+        assert_eq!(
+            installer_args(Some("D:\\Mendix\\10.24.0")),
+            vec![
+                "/SILENT",
+                "/SUPPRESSMSGBOXES",
+                "/NORESTART",
+                "/DIR=D:\\Mendix\\10.24.0"
+            ]
+        );
+        assert_eq!(installer_args(None).len(), 3);
+    }
+
+    #[cfg(unix)]
+    fn fake_installer(dir: &Path, name: &str, script: &str) -> std::path::PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = dir.join(name);
+        std::fs::write(&path, format!("#!/bin/sh\n{}\n", script)).unwrap();
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_run_installer_and_wait() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let failing = fake_installer(dir.path(), "failing.sh", "exit 3");
+        let exit = run_installer_and_wait(&failing, &[], Duration::from_secs(10))
+            .await
+            .unwrap();
+        assert_eq!(
+            exit,
+            InstallerExit {
+                exit_code: Some(3),
+                timed_out: false
+            }
+        );
+
+        let slow = fake_installer(dir.path(), "slow.sh", "sleep 5");
+        let exit = run_installer_and_wait(&slow, &[], Duration::from_millis(100))
+            .await
+            .unwrap();
+        assert!(exit.timed_out);
+    }
+
+    #[test]
+    fn test_download_registry() {
+        let guard = register_download("10.4.0-registry-test").unwrap();
+        assert!(register_download("10.4.0-registry-test").is_err());
+
+        assert!(cancel_download("10.4.0-registry-test"));
+        assert!(guard.is_cancelled());
+
+        drop(guard);
+        assert!(!cancel_download("10.4.0-registry-test"));
+    }
+}
@@ -0,0 +1,176 @@
+use chrono::Utc;
+use std::collections::HashSet;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::state::{AppState, DownloadQueueState, DownloadQueueStatus};
+use crate::storage::{load_download_queue, save_download_queue};
+
+use super::download::cancel_download;
+use super::install_mendix_version;
+
+/// Tauri event carrying the whole `DownloadQueueState` after every change
+pub const DOWNLOAD_QUEUE_EVENT: &str = "mendix-download-queue-changed";
+
+/// Apply a change to the queue, then persist it, mirror it into `VersionOpsState`
+/// and notify the frontend
+fn update_queue<R>(
+    app_handle: &AppHandle,
+    change: impl FnOnce(&mut DownloadQueueState) -> R,
+) -> Result<(R, DownloadQueueState), String> {
+    let state = app_handle.state::<AppState>();
+
+    let (result, previous_versions, snapshot) = {
+        let mut queue = state
+            .download_queue
+            .lock()
+            .map_err(|e| format!("Failed to lock download queue: {}", e))?;
+        let previous_versions = queue.versions();
+        let result = change(&mut queue);
+        (result, previous_versions, queue.clone())
+    };
+
+    {
+        let mut version_ops = state
+            .version_ops
+            .lock()
+            .map_err(|e| format!("Failed to lock version ops state: {}", e))?;
+        let versions: HashSet<String> = previous_versions
+            .into_iter()
+            .chain(snapshot.versions())
+            .collect();
+        for version in versions {
+            let status = snapshot.status_of(&version);
+            version_ops.set_queue_status(version, status);
+        }
+    }
+
+    if let Err(e) = save_download_queue(&snapshot) {
+        println!("[Download Queue] {}", e);
+    }
+    let _ = app_handle.emit(DOWNLOAD_QUEUE_EVENT, snapshot.clone());
+
+    Ok((result, snapshot))
+}
+
+async fn run_queue_item(app_handle: AppHandle, version: String) {
+    let result = install_mendix_version(&app_handle, &version, |stage| {
+        let _ = update_queue(&app_handle, |queue| queue.set_status(&version, stage, None));
+        // The download slot is free once the installer is fetched, so the next item can start
+        if stage == DownloadQueueStatus::Waiting {
+            start_queued_downloads(&app_handle);
+        }
+    })
+    .await;
+
+    let _ = update_queue(&app_handle, |queue| match result {
+        Ok(()) => {
+            println!("[Download Queue] {} installed", version);
+            queue.remove(&version);
+        }
+        Err(error) => {
+            println!("[Download Queue] {} failed: {}", version, error);
+            queue.set_status(&version, DownloadQueueStatus::Failed, Some(error));
+        }
+    });
+
+    start_queued_downloads(&app_handle);
+}
+
+/// Start queued items while concurrency slots are free
+fn start_queued_downloads(app_handle: &AppHandle) {
+    let started = match update_queue(app_handle, DownloadQueueState::start_next) {
+        Ok((started, _)) => started,
+        Err(e) => {
+            println!("[Download Queue] {}", e);
+            return;
+        }
+    };
+
+    for version in started {
+        println!("[Download Queue] Starting {}", version);
+        tauri::async_runtime::spawn(run_queue_item(app_handle.clone(), version));
+    }
+}
+
+/// Restore the persisted queue on startup; interrupted items are requeued and resume
+pub fn resume_download_queue(app_handle: &AppHandle) -> Result<(), String> {
+    let mut stored = load_download_queue();
+    stored.reset_interrupted();
+
+    update_queue(app_handle, |queue| *queue = stored)?;
+    start_queued_downloads(app_handle);
+    Ok(())
+}
+
+#[tauri::command]
+pub fn enqueue_mendix_downloads(
+    app_handle: AppHandle,
+    versions: Vec<String>,
+) -> Result<DownloadQueueState, String> {
+    let now = Utc::now();
+    update_queue(&app_handle, |queue| {
+        for version in &versions {
+            queue.enqueue(version, now);
+        }
+    })?;
+
+    start_queued_downloads(&app_handle);
+    get_download_queue(app_handle.state::<AppState>())
+}
+
+#[tauri::command]
+pub fn get_download_queue(state: State<'_, AppState>) -> Result<DownloadQueueState, String> {
+    state
+        .download_queue
+        .lock()
+        .map(|queue| queue.clone())
+        .map_err(|e| format!("Failed to lock download queue: {}", e))
+}
+
+/// Remove a version from the queue, cancelling it when it is running
+#[tauri::command]
+pub fn remove_from_download_queue(
+    app_handle: AppHandle,
+    version: String,
+) -> Result<DownloadQueueState, String> {
+    cancel_download(&version);
+    let (_, snapshot) = update_queue(&app_handle, |queue| queue.remove(&version))?;
+
+    start_queued_downloads(&app_handle);
+    Ok(snapshot)
+}
+
+#[tauri::command]
+pub fn move_download_queue_item(
+    app_handle: AppHandle,
+    version: String,
+    index: usize,
+) -> Result<DownloadQueueState, String> {
+    let (result, snapshot) = update_queue(&app_handle, |queue| queue.move_item(&version, index))?;
+    result.map(|_| snapshot)
+}
+
+#[tauri::command]
+pub fn retry_download_queue_item(
+    app_handle: AppHandle,
+    version: String,
+) -> Result<DownloadQueueState, String> {
+    let (result, _) = update_queue(&app_handle, |queue| queue.retry(&version))?;
+    result?;
+
+    start_queued_downloads(&app_handle);
+    get_download_queue(app_handle.state::<AppState>())
+}
+
+#[tauri::command]
+pub fn set_download_queue_concurrency(
+    app_handle: AppHandle,
+    max_concurrent: usize,
+) -> Result<DownloadQueueState, String> {
+    update_queue(&app_handle, |queue| {
+        queue.set_max_concurrent(max_concurrent)
+    })?;
+
+    start_queued_downloads(&app_handle);
+    get_download_queue(app_handle.state::<AppState>())
+}
@@ -0,0 +1,238 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use tauri::{AppHandle, Emitter};
+
+use crate::config::ScraperConfig;
+use crate::mendix::{get_installed_mendix_apps, MendixApp};
+use crate::storage::{load_downloadable_versions_cache, load_downloadable_versions_catalogue};
+
+use super::config::{parse_version_parts, split_major_minor, CatalogueImport, DownloadableVersion};
+use super::pagination::walk_datagrid_pages;
+
+/// Tauri event carrying `Vec<NewVersionNotice>` when a refresh finds new releases
+pub const NEW_VERSIONS_EVENT: &str = "mendix-new-versions-available";
+
+/// Delay before the first staleness check after startup
+const FRESHNESS_STARTUP_DELAY_SECS: u64 = 60;
+
+/// Interval between staleness checks of the downloadable versions cache
+const FRESHNESS_CHECK_INTERVAL_SECS: u64 = 15 * 60;
+
+/// Datagrid pages scraped by a background refresh; new releases are listed first
+const BACKGROUND_REFRESH_PAGES: u32 = 3;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VersionsCacheStatus {
+    pub last_refreshed_at: Option<DateTime<Utc>>,
+    pub ttl_hours: u32,
+    pub is_stale: bool,
+    pub last_import: Option<CatalogueImport>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewVersionNotice {
+    pub version: DownloadableVersion,
+    /// Apps on an older patch of the same major.minor
+    pub apps_to_update: Vec<String>,
+}
+
+/// A cache never refreshed is stale; a TTL of 0 never goes stale
+pub fn is_cache_stale(
+    last_refreshed_at: Option<DateTime<Utc>>,
+    ttl_hours: u32,
+    now: DateTime<Utc>,
+) -> bool {
+    if ttl_hours == 0 {
+        return false;
+    }
+
+    last_refreshed_at
+        .is_none_or(|refreshed_at| now - refreshed_at >= Duration::hours(i64::from(ttl_hours)))
+}
+
+fn apps_on_older_patch(version: &str, apps: &[MendixApp]) -> Vec<String> {
+    let major_minor = split_major_minor(version);
+    let parts = parse_version_parts(version);
+
+    apps.iter()
+        .filter(|app| {
+            app.version.as_deref().is_some_and(|app_version| {
+                split_major_minor(app_version) == major_minor
+                    && parse_version_parts(app_version) < parts
+            })
+        })
+        .map(|app| app.name.clone())
+        .collect()
+}
+
+/// Versions absent from the previous snapshot that are LTS, MTS, latest, or a newer patch
+/// for an app; nothing is reported when there was no previous snapshot
+pub fn detect_new_versions(
+    previous: &[DownloadableVersion],
+    current: &[DownloadableVersion],
+    apps: &[MendixApp],
+) -> Vec<NewVersionNotice> {
+    if previous.is_empty() {
+        return Vec::new();
+    }
+
+    let known: HashSet<&str> = previous.iter().map(|v| v.version.as_str()).collect();
+
+    current
+        .iter()
+        .filter(|version| !known.contains(version.version.as_str()))
+        .filter_map(|version| {
+            let apps_to_update = apps_on_older_patch(&version.version, apps);
+            let is_notable = version.is_lts || version.is_mts || version.is_latest;
+
+            (is_notable || !apps_to_update.is_empty()).then(|| NewVersionNotice {
+                version: version.clone(),
+                apps_to_update,
+            })
+        })
+        .collect()
+}
+
+/// Emit `NEW_VERSIONS_EVENT` for releases that appeared since `previous`
+pub(crate) fn notify_new_versions(
+    app_handle: &AppHandle,
+    previous: &[DownloadableVersion],
+    current: &[DownloadableVersion],
+) {
+    let has_unseen = current.iter().any(|version| {
+        !previous
+            .iter()
+            .any(|known| known.version == version.version)
+    });
+    if previous.is_empty() || !has_unseen {
+        return;
+    }
+
+    let apps = get_installed_mendix_apps().unwrap_or_default();
+    let notices = detect_new_versions(previous, current, &apps);
+    if notices.is_empty() {
+        return;
+    }
+
+    println!("[Versions Cache] {} new versions available", notices.len());
+    let _ = app_handle.emit(NEW_VERSIONS_EVENT, notices);
+}
+
+fn versions_cache_status() -> VersionsCacheStatus {
+    let ttl_hours = ScraperConfig::load()
+        .unwrap_or_default()
+        .versions_cache_ttl_hours();
+    let catalogue = load_downloadable_versions_catalogue();
+
+    VersionsCacheStatus {
+        last_refreshed_at: catalogue.last_refreshed_at,
+        ttl_hours,
+        is_stale: is_cache_stale(catalogue.last_refreshed_at, ttl_hours, Utc::now()),
+        last_import: catalogue.last_import,
+    }
+}
+
+async fn refresh_if_stale(app_handle: &AppHandle) -> Result<(), String> {
+    if !versions_cache_status().is_stale {
+        return Ok(());
+    }
+
+    println!("[Versions Cache] Cache is stale, refreshing");
+    let previous = load_downloadable_versions_cache().unwrap_or_default();
+    let summary = walk_datagrid_pages(app_handle, None, BACKGROUND_REFRESH_PAGES).await?;
+    notify_new_versions(app_handle, &previous, &summary.versions);
+    Ok(())
+}
+
+/// Periodically refresh the downloadable versions cache once it outlives its TTL
+pub fn start_versions_cache_refresh(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_secs(FRESHNESS_STARTUP_DELAY_SECS)).await;
+
+        loop {
+            if let Err(e) = refresh_if_stale(&app_handle).await {
+                println!("[Versions Cache] Refresh failed: {}", e);
+            }
+            tokio::time::sleep(std::time::Duration::from_secs(
+                FRESHNESS_CHECK_INTERVAL_SECS,
+            ))
+            .await;
+        }
+    });
+}
+
+#[tauri::command]
+pub fn get_versions_cache_status() -> Result<VersionsCacheStatus, String> {
+    Ok(versions_cache_status())
+}
+
+/// Save the cache TTL in hours; 0 turns background refresh off
+#[tauri::command]
+pub fn save_versions_cache_ttl(ttl_hours: u32) -> Result<VersionsCacheStatus, String> {
+    ScraperConfig::load()
+        .unwrap_or_default()
+        .with_versions_cache_ttl_hours(ttl_hours)
+        .save()?;
+    Ok(versions_cache_status())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::web_scraper::config::VersionFlags;
+
+    fn downloadable(version: &str, flags: VersionFlags) -> DownloadableVersion {
+        DownloadableVersion::new(version.to_string(), String::new(), None, None, None, flags)
+    }
+
+    fn app(name: &str, version: &str) -> MendixApp {
+        MendixApp {
+            name: name.to_string(),
+            path: format!("C:/apps/{}", name),
+            version: Some(version.to_string()),
+            build_number: None,
+            last_modified: None,
+            is_valid: true,
+            mpr_path: None,
+            project_id: None,
+            modules: Vec::new(),
+            git_status: None,
+        }
+    }
+
+    #[test]
+    fn test_is_cache_stale() {
+        let now = Utc::now();
+
+        assert!(is_cache_stale(None, 24, now));
+        assert!(!is_cache_stale(Some(now - Duration::hours(2)), 24, now));
+        assert!(is_cache_stale(Some(now - Duration::hours(25)), 24, now));
+        assert!(!is_cache_stale(None, 0, now));
+    }
+
+    #[test]
+    fn test_detect_new_versions() {
+        let previous = vec![
+            downloadable("10.24.0", VersionFlags::new(true, false, false, false)),
+            downloadable("10.23.0", VersionFlags::default()),
+        ];
+        let current = vec![
+            downloadable("11.0.0", VersionFlags::new(false, false, false, true)),
+            downloadable("10.24.1", VersionFlags::default()),
+            downloadable("10.23.1", VersionFlags::default()),
+            downloadable("10.24.0", VersionFlags::new(true, false, false, false)),
+        ];
+        let apps = vec![app("Portal", "10.24.0"), app("Legacy", "9.24.0")];
+
+        let notices = detect_new_versions(&previous, &current, &apps);
+
+        assert_eq!(notices.len(), 2);
+        assert_eq!(notices[0].version.version, "11.0.0");
+        assert!(notices[0].apps_to_update.is_empty());
+        assert_eq!(notices[1].version.version, "10.24.1");
+        assert_eq!(notices[1].apps_to_update, vec!["Portal"]);
+
+        assert!(detect_new_versions(&[], &current, &apps).is_empty());
+    }
+}
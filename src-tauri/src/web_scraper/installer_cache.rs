@@ -0,0 +1,313 @@
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Installer cache folder under the local data directory
+const INSTALLER_CACHE_DIR: &str = "installer_cache";
+
+/// Extension of the metadata file stored next to each cached installer
+const CACHE_INFO_EXTENSION: &str = "json";
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CachedInstaller {
+    pub file_name: String,
+    pub version: String,
+    pub sha256: String,
+    pub size_bytes: u64,
+    pub cached_at: DateTime<Utc>,
+    pub last_used_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstallerVerification {
+    pub installer: CachedInstaller,
+    pub is_valid: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstallerCachePruneResult {
+    pub removed: Vec<CachedInstaller>,
+    pub reclaimed_bytes: u64,
+    pub remaining_bytes: u64,
+}
+
+/// Resolve the installer cache directory for the current user
+pub fn get_installer_cache_directory() -> Result<PathBuf, String> {
+    dirs::data_local_dir()
+        .ok_or_else(|| "Could not find local data directory".to_string())
+        .map(|dir| {
+            dir.join("kirakiraichigo-mendix-manager")
+                .join(INSTALLER_CACHE_DIR)
+        })
+}
+
+fn cache_info_path(cache_dir: &Path, file_name: &str) -> PathBuf {
+    cache_dir.join(format!("{}.{}", file_name, CACHE_INFO_EXTENSION))
+}
+
+/// Hex-encoded SHA-256 of a file, read in chunks
+pub fn compute_file_sha256(path: &Path) -> Result<String, String> {
+    let mut file = fs::File::open(path).map_err(|e| format!("Failed to open file: {}", e))?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 1024 * 1024];
+
+    loop {
+        let read = file
+            .read(&mut buffer)
+            .map_err(|e| format!("Failed to read file: {}", e))?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+
+    Ok(hasher
+        .finalize()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect())
+}
+
+fn write_cache_info(cache_dir: &Path, info: &CachedInstaller) -> Result<(), String> {
+    let content = serde_json::to_string_pretty(info)
+        .map_err(|e| format!("Failed to serialize installer info: {}", e))?;
+    fs::write(cache_info_path(cache_dir, &info.file_name), content)
+        .map_err(|e| format!("Failed to write installer info: {}", e))
+}
+
+fn read_cache_info(info_path: &Path) -> Option<CachedInstaller> {
+    fs::read_to_string(info_path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+}
+
+/// Hash a freshly downloaded installer in the cache directory and record it
+pub fn record_cached_installer(
+    cache_dir: &Path,
+    file_name: &str,
+    version: &str,
+    now: DateTime<Utc>,
+) -> Result<CachedInstaller, String> {
+    let installer_path = cache_dir.join(file_name);
+    let size_bytes = fs::metadata(&installer_path)
+        .map_err(|e| format!("Failed to read installer: {}", e))?
+        .len();
+
+    let info = CachedInstaller {
+        file_name: file_name.to_string(),
+        version: version.to_string(),
+        sha256: compute_file_sha256(&installer_path)?,
+        size_bytes,
+        cached_at: now,
+        last_used_at: now,
+    };
+    write_cache_info(cache_dir, &info)?;
+
+    Ok(info)
+}
+
+fn verify_installer(cache_dir: &Path, info: &CachedInstaller) -> Result<(), String> {
+    let installer_path = cache_dir.join(&info.file_name);
+    if !installer_path.is_file() {
+        return Err(format!("Installer file is missing: {}", info.file_name));
+    }
+
+    let actual = compute_file_sha256(&installer_path)?;
+    if actual != info.sha256 {
+        return Err(format!(
+            "Checksum mismatch for {}: expected {}, found {}",
+            info.file_name, info.sha256, actual
+        ));
+    }
+
+    Ok(())
+}
+
+fn remove_cached_installer(cache_dir: &Path, info: &CachedInstaller) -> Result<(), String> {
+    let installer_path = cache_dir.join(&info.file_name);
+    if installer_path.exists() {
+        fs::remove_file(&installer_path)
+            .map_err(|e| format!("Failed to remove {}: {}", info.file_name, e))?;
+    }
+    fs::remove_file(cache_info_path(cache_dir, &info.file_name))
+        .map_err(|e| format!("Failed to remove installer info: {}", e))
+}
+
+/// Return a cached installer whose checksum still matches; corrupt entries are dropped
+pub fn find_verified_installer(
+    cache_dir: &Path,
+    file_name: &str,
+    now: DateTime<Utc>,
+) -> Option<CachedInstaller> {
+    let info = read_cache_info(&cache_info_path(cache_dir, file_name))?;
+
+    if let Err(error) = verify_installer(cache_dir, &info) {
+        println!("[Installer Cache] Discarding cached installer: {}", error);
+        let _ = remove_cached_installer(cache_dir, &info);
+        return None;
+    }
+
+    let info = CachedInstaller {
+        last_used_at: now,
+        ..info
+    };
+    let _ = write_cache_info(cache_dir, &info);
+    Some(info)
+}
+
+/// List cached installers, most recently used first
+pub fn list_cache_entries(cache_dir: &Path) -> Vec<CachedInstaller> {
+    let mut entries: Vec<CachedInstaller> = fs::read_dir(cache_dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|entry| entry.path())
+                .filter(|path| {
+                    path.extension()
+                        .is_some_and(|extension| extension == CACHE_INFO_EXTENSION)
+                })
+                .filter_map(|path| read_cache_info(&path))
+                .collect()
+        })
+        .unwrap_or_default();
+    entries.sort_by_key(|entry| std::cmp::Reverse(entry.last_used_at));
+    entries
+}
+
+/// Remove installers unused for longer than `max_age_days`, then the least recently
+/// used ones until the cache fits in `max_total_bytes`
+pub fn prune_cache_entries(
+    cache_dir: &Path,
+    max_age_days: Option<u32>,
+    max_total_bytes: Option<u64>,
+    now: DateTime<Utc>,
+) -> InstallerCachePruneResult {
+    let cutoff = max_age_days.map(|days| now - Duration::days(i64::from(days)));
+    let mut remaining_bytes: u64 = 0;
+    let mut removed = Vec::new();
+
+    for entry in list_cache_entries(cache_dir) {
+        let expired = cutoff.is_some_and(|cutoff| entry.last_used_at < cutoff);
+        let over_budget =
+            max_total_bytes.is_some_and(|max| remaining_bytes + entry.size_bytes > max);
+
+        if (expired || over_budget) && remove_cached_installer(cache_dir, &entry).is_ok() {
+            removed.push(entry);
+        } else {
+            remaining_bytes += entry.size_bytes;
+        }
+    }
+
+    InstallerCachePruneResult {
+        reclaimed_bytes: removed.iter().map(|entry| entry.size_bytes).sum(),
+        removed,
+        remaining_bytes,
+    }
+}
+
+#[tauri::command]
+pub fn list_cached_installers() -> Result<Vec<CachedInstaller>, String> {
+    Ok(list_cache_entries(&get_installer_cache_directory()?))
+}
+
+#[tauri::command]
+pub fn verify_cached_installers() -> Result<Vec<InstallerVerification>, String> {
+    let cache_dir = get_installer_cache_directory()?;
+
+    Ok(list_cache_entries(&cache_dir)
+        .into_iter()
+        .map(|installer| {
+            let error = verify_installer(&cache_dir, &installer).err();
+            InstallerVerification {
+                installer,
+                is_valid: error.is_none(),
+                error,
+            }
+        })
+        .collect())
+}
+
+#[tauri::command]
+pub fn prune_installer_cache(
+    max_age_days: Option<u32>,
+    max_total_bytes: Option<u64>,
+) -> Result<InstallerCachePruneResult, String> {
+    let result = prune_cache_entries(
+        &get_installer_cache_directory()?,
+        max_age_days,
+        max_total_bytes,
+        Utc::now(),
+    );
+
+    println!(
+        "[Installer Cache] Removed {} installers, reclaimed {} bytes",
+        result.removed.len(),
+        result.reclaimed_bytes
+    );
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cache_installer(
+        cache_dir: &Path,
+        version: &str,
+        size: usize,
+        used_at: DateTime<Utc>,
+    ) -> CachedInstaller {
+        let file_name = format!("Mendix-{}-Setup.exe", version);
+        fs::write(cache_dir.join(&file_name), vec![1u8; size]).unwrap();
+        record_cached_installer(cache_dir, &file_name, version, used_at).unwrap()
+    }
+
+    #[test]
+    fn test_compute_file_sha256() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("file.bin");
+        fs::write(&path, b"abc").unwrap();
+
+        assert_eq!(
+            compute_file_sha256(&path).unwrap(),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn test_find_verified_installer_discards_corrupt_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let now = Utc::now();
+        let cached = cache_installer(dir.path(), "10.4.0", 100, now);
+
+        let found = find_verified_installer(dir.path(), &cached.file_name, now).unwrap();
+        assert_eq!(found.sha256, cached.sha256);
+
+        fs::write(dir.path().join(&cached.file_name), b"tampered").unwrap();
+
+        assert!(find_verified_installer(dir.path(), &cached.file_name, now).is_none());
+        assert!(!dir.path().join(&cached.file_name).exists());
+        assert!(list_cache_entries(dir.path()).is_empty());
+    }
+
+    #[test]
+    fn test_prune_cache_entries_by_age_and_size() {
+        let dir = tempfile::tempdir().unwrap();
+        let now = Utc::now();
+        let old = cache_installer(dir.path(), "9.24.0", 100, now - Duration::days(90));
+        let older_recent = cache_installer(dir.path(), "10.3.0", 300, now - Duration::days(5));
+        let newest = cache_installer(dir.path(), "10.4.0", 300, now - Duration::days(1));
+
+        let result = prune_cache_entries(dir.path(), Some(30), Some(400), now);
+
+        assert_eq!(result.removed, vec![older_recent, old]);
+        assert_eq!(result.reclaimed_bytes, 400);
+        assert_eq!(result.remaining_bytes, 300);
+        assert_eq!(list_cache_entries(dir.path()), vec![newest]);
+    }
+}
@@ -1,28 +1,139 @@
 mod browser;
+mod browser_detection;
+mod catalogue;
 mod config;
 mod download;
+mod download_queue;
+mod freshness;
+mod installer_cache;
+mod network;
+mod pagination;
 mod parsing;
+mod release_notes;
+mod selectors;
 
-use std::time::Duration;
+use chrono::Utc;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+use crate::config::NetworkSettings;
+use crate::storage::{load_downloadable_versions_cache, update_cached_downloadable_version};
+use crate::mendix::{wait_for_installed_version, InstallResult};
+use crate::state::DownloadQueueStatus;
 
 use browser::{handle_privacy_modal_if_present, navigate_to_page, BrowserSession};
 use config::{
-    construct_download_url, construct_download_url_v11, construct_marketplace_url,
+    construct_artifact_file_name, construct_download_url, construct_marketplace_url,
     is_version_11_or_above, ScrapingConfig, PAGE_LOAD_DELAY_MS,
 };
-use download::{download_file_to_path, execute_installer};
+use download::{
+    cancel_download, download_file_to_path, execute_installer, installer_args, register_download,
+    run_installer_and_wait, DownloadGuard,
+};
+use installer_cache::{
+    find_verified_installer, get_installer_cache_directory, list_cache_entries,
+    record_cached_installer,
+};
+use network::{
+    artifacts_base_url, find_installer_in_repository, is_installer_for_version,
+    load_network_settings, marketplace_url,
+};
 use parsing::{extract_build_number_from_marketplace, extract_datagrid_content, parse_datagrid_html};
+use selectors::load_selector_profile;
+
+/// Tauri event carrying `DownloadProgress` while an installer downloads
+pub const DOWNLOAD_PROGRESS_EVENT: &str = "mendix-download-progress";
+
+/// Default time allowed for an installer to finish, in seconds
+const DEFAULT_INSTALL_TIMEOUT_SECS: u64 = 15 * 60;
 
 // Re-export public types
-pub use config::{BuildInfo, DownloadProgress, DownloadableVersion, DownloadableVersionsCache};
+pub use browser_detection::{
+    detect_browser_executable, save_browser_path, BrowserDetection, BrowserSource,
+};
+pub use catalogue::{
+    export_downloadable_versions_catalogue, import_downloadable_versions_catalogue,
+};
+pub use config::{
+    ArtifactPlatform, BuildInfo, CatalogueImport, DownloadProgress, DownloadableVersion,
+    DownloadableVersionsCache, PlatformArtifact,
+};
+pub use download_queue::{
+    enqueue_mendix_downloads, get_download_queue, move_download_queue_item,
+    remove_from_download_queue, resume_download_queue, retry_download_queue_item,
+    set_download_queue_concurrency, DOWNLOAD_QUEUE_EVENT,
+};
+pub use pagination::{
+    scrape_all_downloadable_versions, DatagridScrapeSummary, DownloadableVersionsPage,
+    DOWNLOADABLE_VERSIONS_PAGE_EVENT,
+};
+pub use release_notes::{
+    get_release_notes, get_release_notes_changelog, ChangelogEntry, ChangelogSection,
+    ReleaseNoteCategory, ReleaseNoteSection, ReleaseNotesChangelog, VersionReleaseNotes,
+};
+pub use freshness::{
+    get_versions_cache_status, save_versions_cache_ttl, start_versions_cache_refresh,
+    NewVersionNotice, VersionsCacheStatus, NEW_VERSIONS_EVENT,
+};
+pub use installer_cache::{
+    list_cached_installers, prune_installer_cache, verify_cached_installers, CachedInstaller,
+    InstallerCachePruneResult, InstallerVerification,
+};
+pub use network::{get_network_settings, save_network_settings};
+pub use selectors::{get_selector_profile, save_selector_profile, SelectorProfile};
+
+/// Create BuildInfo from build number and version for the installer of `platform`
+fn create_build_info(
+    network: &NetworkSettings,
+    build_number: String,
+    version: &str,
+    platform: ArtifactPlatform,
+) -> Result<BuildInfo, String> {
+    let download_url = construct_download_url(
+        artifacts_base_url(network),
+        version,
+        Some(&build_number),
+        platform,
+    )
+    .ok_or_else(|| no_installer_error(version, platform))?;
 
-/// Create BuildInfo from build number and version
-fn create_build_info(build_number: String, version: &str) -> BuildInfo {
-    let download_url = construct_download_url(version, &build_number);
-    BuildInfo {
+    Ok(BuildInfo {
         build_number,
         download_url,
-    }
+    })
+}
+
+fn no_installer_error(version: &str, platform: ArtifactPlatform) -> String {
+    format!(
+        "Mendix Studio Pro {} has no {} installer",
+        version,
+        platform.label()
+    )
+}
+
+/// Wait for the single install slot; installers run one at a time while downloads stay parallel
+async fn acquire_install_permit() -> Result<SemaphorePermit<'static>, String> {
+    static INSTALL_PERMIT: OnceLock<Semaphore> = OnceLock::new();
+
+    INSTALL_PERMIT
+        .get_or_init(|| Semaphore::new(1))
+        .acquire()
+        .await
+        .map_err(|e| format!("Failed to wait for the install slot: {}", e))
+}
+
+/// Platform whose installer is downloaded on this machine
+fn current_artifact_platform() -> Result<ArtifactPlatform, String> {
+    ArtifactPlatform::current().ok_or_else(|| {
+        format!(
+            "Mendix Studio Pro has no installer for {} ({})",
+            std::env::consts::OS,
+            std::env::consts::ARCH
+        )
+    })
 }
 
 #[tauri::command]
@@ -30,23 +141,28 @@ pub async fn get_downloadable_versions_from_datagrid(
     page: Option<u32>,
 ) -> Result<Vec<DownloadableVersion>, String> {
     let config = ScrapingConfig::default();
-    let url = config::MENDIX_MARKETPLACE_URL;
+    let network = load_network_settings();
+    let profile = load_selector_profile();
 
     let session = BrowserSession::new().await?;
 
     let result = async {
-        let page_instance = session.navigate(url).await?;
+        let page_instance = session.navigate(marketplace_url(&network)).await?;
 
-        handle_privacy_modal_if_present(&page_instance).await?;
+        handle_privacy_modal_if_present(&page_instance, &profile).await?;
 
         if let Some(target_page) = page {
             if target_page > 1 {
-                navigate_to_page(&page_instance, target_page).await?;
+                navigate_to_page(&page_instance, target_page, &profile).await?;
             }
         }
 
-        let html_content = extract_datagrid_content(&page_instance, &config).await?;
-        parse_datagrid_html(&html_content)
+        let html_content = extract_datagrid_content(&page_instance, &config, &profile).await?;
+        let versions = parse_datagrid_html(&html_content, &profile)?;
+        Ok(versions
+            .into_iter()
+            .map(|version| version.with_platform_artifacts(artifacts_base_url(&network)))
+            .collect())
     }
     .await;
 
@@ -55,18 +171,22 @@ pub async fn get_downloadable_versions_from_datagrid(
 }
 
 /// Extract build number for a specific version (used internally)
-async fn extract_build_number(version: &str) -> Result<BuildInfo, String> {
-    let url = construct_marketplace_url(version);
+async fn extract_build_number(
+    network: &NetworkSettings,
+    version: &str,
+    platform: ArtifactPlatform,
+) -> Result<BuildInfo, String> {
+    let url = construct_marketplace_url(marketplace_url(network), version);
+    let profile = load_selector_profile();
     let session = BrowserSession::new().await?;
 
     tokio::time::sleep(Duration::from_millis(PAGE_LOAD_DELAY_MS)).await;
 
     let result = async {
         let page = session.navigate(&url).await?;
-        handle_privacy_modal_if_present(&page).await?;
-        let build_number = extract_build_number_from_marketplace(&page, version).await?;
-        let build_info = create_build_info(build_number, version);
-        Ok(build_info)
+        handle_privacy_modal_if_present(&page, &profile).await?;
+        let build_number = extract_build_number_from_marketplace(&page, version, &profile).await?;
+        create_build_info(network, build_number, version, platform)
     }
     .await;
 
@@ -74,38 +194,367 @@ async fn extract_build_number(version: &str) -> Result<BuildInfo, String> {
     result
 }
 
-#[tauri::command]
-pub async fn download_and_install_mendix_version(version: String) -> Result<String, String> {
-    let downloads_dir =
-        dirs::download_dir().ok_or_else(|| "Failed to get downloads directory".to_string())?;
+/// Build number of a version from the versions cache, or from its marketplace page, in which
+/// case it is cached so later downloads and catalogue exports have it
+async fn resolve_build_number(
+    network: &NetworkSettings,
+    version: &str,
+    platform: ArtifactPlatform,
+) -> Result<String, String> {
+    let cached = load_downloadable_versions_cache()
+        .unwrap_or_default()
+        .into_iter()
+        .find(|cached| cached.version == version)
+        .and_then(|cached| cached.build_number);
+    if let Some(build_number) = cached {
+        println!("📋 Using cached build number {}", build_number);
+        return Ok(build_number);
+    }
+
+    println!("📋 Extracting build number...");
+    let build_number = extract_build_number(network, version, platform)
+        .await?
+        .build_number;
+
+    let base_url = artifacts_base_url(network);
+    if let Err(e) = update_cached_downloadable_version(version, |cached| {
+        cached
+            .with_build_number(Some(build_number.clone()))
+            .with_platform_artifacts(base_url)
+    }) {
+        println!("[Download] Failed to cache build number of {}: {}", version, e);
+    }
+
+    Ok(build_number)
+}
+
+/// Hash a cached installer on a blocking thread and record it
+async fn record_installer_checksum(
+    cache_dir: &Path,
+    file_name: &str,
+    version: &str,
+) -> Result<(), String> {
+    let cache_dir = cache_dir.to_path_buf();
+    let file_name = file_name.to_string();
+    let version = version.to_string();
+
+    tokio::task::spawn_blocking(move || {
+        record_cached_installer(&cache_dir, &file_name, &version, Utc::now())
+    })
+    .await
+    .map_err(|e| format!("Failed to record installer checksum: {}", e))??;
 
-    let (download_url, installer_filename) = if is_version_11_or_above(&version) {
+    Ok(())
+}
+
+/// Download the installer of a version into the cache and record its checksum
+async fn download_installer_to_cache<F>(
+    app_handle: &AppHandle,
+    network: &NetworkSettings,
+    version: &str,
+    platform: ArtifactPlatform,
+    cache_dir: &Path,
+    download_guard: &DownloadGuard,
+    on_stage: &mut F,
+) -> Result<String, String>
+where
+    F: FnMut(DownloadQueueStatus),
+{
+    if !platform.is_available_for(version) {
+        return Err(no_installer_error(version, platform));
+    }
+
+    let build_number = if is_version_11_or_above(version) {
         println!("📋 Version 11+ detected, skipping build number extraction...");
-        let url = construct_download_url_v11(&version);
-        let filename = format!("Mendix-{}-Setup.exe", version);
-        (url, filename)
+        None
     } else {
-        println!("📋 Extracting build number...");
-        let build_info = extract_build_number(&version).await?;
-        let filename = format!("Mendix-{}.{}-Setup.exe", version, build_info.build_number);
-        (build_info.download_url, filename)
+        Some(resolve_build_number(network, version, platform).await?)
     };
 
+    let installer_filename =
+        construct_artifact_file_name(version, build_number.as_deref(), platform)
+            .ok_or_else(|| no_installer_error(version, platform))?;
+    let download_url = format!("{}/{}", artifacts_base_url(network), installer_filename);
+    println!(
+        "📋 Selected {} installer {}",
+        platform.label(),
+        installer_filename
+    );
+
     println!("📁 Setting up download path...");
-    let installer_path = downloads_dir.join(&installer_filename);
+    let installer_path = cache_dir.join(&installer_filename);
     let installer_path_str = installer_path
         .to_str()
         .ok_or_else(|| "Invalid installer path".to_string())?;
 
+    if download_guard.is_cancelled() {
+        return Err("Download cancelled".to_string());
+    }
+
     println!("⬇️ Downloading installer...");
-    download_file_to_path(&download_url, installer_path_str).await?;
+    download_file_to_path(
+        network,
+        &download_url,
+        installer_path_str,
+        download_guard.cancel_signal(),
+        |downloaded_bytes, total_bytes| {
+            let progress = DownloadProgress::new(version, downloaded_bytes, total_bytes);
+            let _ = app_handle.emit(DOWNLOAD_PROGRESS_EVENT, progress);
+        },
+    )
+    .await?;
+
+    println!("🔐 Recording installer checksum...");
+    on_stage(DownloadQueueStatus::Verifying);
+    record_installer_checksum(cache_dir, &installer_filename, version).await?;
+
+    Ok(installer_filename)
+}
+
+/// Copy the installer of a version from the local folder repository into the cache
+async fn copy_installer_from_repository<F>(
+    repository_installer: &Path,
+    version: &str,
+    cache_dir: &Path,
+    on_stage: &mut F,
+) -> Result<String, String>
+where
+    F: FnMut(DownloadQueueStatus),
+{
+    let installer_filename = repository_installer
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| "Invalid installer path".to_string())?
+        .to_string();
+
+    println!(
+        "📂 Copying installer from local repository: {}",
+        repository_installer.display()
+    );
+    tokio::fs::copy(repository_installer, cache_dir.join(&installer_filename))
+        .await
+        .map_err(|e| format!("Failed to copy installer from local repository: {}", e))?;
+
+    println!("🔐 Recording installer checksum...");
+    on_stage(DownloadQueueStatus::Verifying);
+    record_installer_checksum(cache_dir, &installer_filename, version).await?;
+
+    Ok(installer_filename)
+}
+
+/// Fetch the installer of a version into the cache, reusing a verified copy when one exists.
+/// `on_stage` is told when the work moves between downloading and verifying.
+async fn fetch_installer<F>(
+    app_handle: &AppHandle,
+    version: &str,
+    on_stage: &mut F,
+) -> Result<PathBuf, String>
+where
+    F: FnMut(DownloadQueueStatus),
+{
+    let platform = current_artifact_platform()?;
+    let download_guard = register_download(version)?;
+    let cache_dir = get_installer_cache_directory()?;
+    std::fs::create_dir_all(&cache_dir)
+        .map_err(|e| format!("Failed to create installer cache directory: {}", e))?;
+
+    let cached_entry = list_cache_entries(&cache_dir).into_iter().find(|entry| {
+        entry.version == version && is_installer_for_version(&entry.file_name, version, platform)
+    });
+    let cached = match cached_entry {
+        Some(entry) => {
+            on_stage(DownloadQueueStatus::Verifying);
+            let cache_dir = cache_dir.clone();
+            tokio::task::spawn_blocking(move || {
+                find_verified_installer(&cache_dir, &entry.file_name, Utc::now())
+            })
+            .await
+            .unwrap_or(None)
+        }
+        None => None,
+    };
+
+    let network = load_network_settings();
+    let repository_installer = network
+        .local_repository_path
+        .as_deref()
+        .and_then(|repository| {
+            find_installer_in_repository(Path::new(repository), version, platform)
+        });
+
+    let installer_filename = match (cached, repository_installer) {
+        (Some(cached), _) => {
+            println!("📦 Using cached installer {}", cached.file_name);
+            cached.file_name
+        }
+        (None, Some(repository_installer)) => {
+            on_stage(DownloadQueueStatus::Downloading);
+            copy_installer_from_repository(
+                &repository_installer,
+                version,
+                &cache_dir,
+                on_stage,
+            )
+            .await?
+        }
+        (None, None) => {
+            on_stage(DownloadQueueStatus::Downloading);
+            download_installer_to_cache(
+                app_handle,
+                &network,
+                version,
+                platform,
+                &cache_dir,
+                &download_guard,
+                on_stage,
+            )
+            .await?
+        }
+    };
+    drop(download_guard);
+
+    Ok(cache_dir.join(installer_filename))
+}
+
+/// Fetch the installer of a version, from the cache when a verified copy exists, and run it.
+/// `on_stage` is told when the work moves between downloading, verifying, waiting for
+/// another installer to finish and installing.
+pub(crate) async fn install_mendix_version<F>(
+    app_handle: &AppHandle,
+    version: &str,
+    mut on_stage: F,
+) -> Result<(), String>
+where
+    F: FnMut(DownloadQueueStatus),
+{
+    let installer_path = fetch_installer(app_handle, version, &mut on_stage).await?;
+    let installer_path_str = installer_path
+        .to_str()
+        .ok_or_else(|| "Invalid installer path".to_string())?;
+
+    on_stage(DownloadQueueStatus::Waiting);
+    let _install_permit = acquire_install_permit().await?;
+    on_stage(DownloadQueueStatus::Installing);
 
     println!("🚀 Launching installer...");
-    execute_installer(installer_path_str)?;
-    println!("✅ Installer launched successfully");
+    if current_artifact_platform()? != ArtifactPlatform::Windows {
+        execute_installer(installer_path_str)?;
+        println!("✅ Installer launched successfully");
+        return Ok(());
+    }
+
+    let exit = run_installer_and_wait(
+        &installer_path,
+        &installer_args(None),
+        Duration::from_secs(DEFAULT_INSTALL_TIMEOUT_SECS),
+    )
+    .await?;
+    if exit.timed_out {
+        return Err(format!("Installer for {} did not finish in time", version));
+    }
+    if exit.exit_code != Some(0) {
+        return Err(format!(
+            "Installer for {} exited with code {:?}",
+            version, exit.exit_code
+        ));
+    }
+    println!("✅ Installer finished successfully");
+
+    Ok(())
+}
+
+/// Download and install a version. On Windows this waits for the installer to exit and
+/// reports its result; elsewhere the installer is only opened for the user to complete.
+#[tauri::command]
+pub async fn download_and_install_mendix_version(
+    app_handle: AppHandle,
+    version: String,
+) -> Result<String, String> {
+    install_mendix_version(&app_handle, &version, |_| {}).await?;
+
+    if current_artifact_platform()? == ArtifactPlatform::Windows {
+        Ok(format!("Successfully installed Mendix Studio Pro {}", version))
+    } else {
+        Ok(format!(
+            "Successfully started installation of Mendix Studio Pro {}",
+            version
+        ))
+    }
+}
+
+/// Cancel the running installer download of a version; the partial file is kept for resuming
+#[tauri::command]
+pub fn cancel_mendix_download(version: String) -> Result<bool, String> {
+    let cancelled = cancel_download(&version);
+    if cancelled {
+        println!("[Download] Cancellation requested for {}", version);
+    }
+    Ok(cancelled)
+}
+
+/// Install a version, optionally into `target_dir`, and wait until the installer exits and
+/// the new installation shows up in the scan. Only the unattended Windows setup can be
+/// waited for; the macOS disk images need the user to drag the app into place.
+#[tauri::command]
+pub async fn install_mendix_version_and_wait(
+    app_handle: AppHandle,
+    version: String,
+    target_dir: Option<String>,
+    timeout_seconds: Option<u64>,
+) -> Result<InstallResult, String> {
+    let platform = current_artifact_platform()?;
+    if platform != ArtifactPlatform::Windows {
+        return Err(format!(
+            "Unattended installs are only supported on Windows; the {} installer has to be opened and completed manually",
+            platform.label()
+        ));
+    }
+
+    let timeout = Duration::from_secs(timeout_seconds.unwrap_or(DEFAULT_INSTALL_TIMEOUT_SECS));
+    let target_dir = target_dir
+        .map(|dir| dir.trim().to_string())
+        .filter(|dir| !dir.is_empty());
+    if let Some(dir) = &target_dir {
+        if !Path::new(dir).is_absolute() {
+            return Err(format!("Install directory must be an absolute path: {}", dir));
+        }
+    }
+
+    let installer_path = fetch_installer(&app_handle, &version, &mut |_| {}).await?;
+
+    let _install_permit = acquire_install_permit().await?;
+    println!("🚀 Running installer and waiting for it to finish...");
+    let started_at = Instant::now();
+    let exit = run_installer_and_wait(
+        &installer_path,
+        &installer_args(target_dir.as_deref()),
+        timeout,
+    )
+    .await?;
+
+    let installed_version = if exit.timed_out || exit.exit_code != Some(0) {
+        None
+    } else {
+        wait_for_installed_version(
+            &version,
+            target_dir.as_deref().map(Path::new),
+            timeout.saturating_sub(started_at.elapsed()),
+        )
+        .await
+    };
+
+    let success = installed_version.is_some();
+    println!(
+        "[Install] {} finished: success={}, exit code={:?}, timed out={}",
+        version, success, exit.exit_code, exit.timed_out
+    );
 
-    Ok(format!(
-        "Successfully started installation of Mendix Studio Pro {}",
-        version
-    ))
+    Ok(InstallResult {
+        success,
+        version,
+        timed_out: exit.timed_out || (exit.exit_code == Some(0) && !success),
+        exit_code: exit.exit_code,
+        install_dir: target_dir,
+        installed_version,
+    })
 }
@@ -0,0 +1,356 @@
+use reqwest::{Certificate, ClientBuilder, NoProxy, Proxy, Url};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::config::{NetworkSettings, ScraperConfig};
+
+use super::config::{ArtifactPlatform, MENDIX_ARTIFACTS_BASE_URL, MENDIX_MARKETPLACE_URL};
+
+pub fn load_network_settings() -> NetworkSettings {
+    ScraperConfig::load().unwrap_or_default().network
+}
+
+pub fn marketplace_url(settings: &NetworkSettings) -> &str {
+    settings
+        .marketplace_url
+        .as_deref()
+        .unwrap_or(MENDIX_MARKETPLACE_URL)
+}
+
+pub fn artifacts_base_url(settings: &NetworkSettings) -> &str {
+    settings
+        .artifacts_base_url
+        .as_deref()
+        .unwrap_or(MENDIX_ARTIFACTS_BASE_URL)
+}
+
+fn load_ca_certificates(path: &str) -> Result<Vec<Certificate>, String> {
+    let pem = fs::read(path).map_err(|e| format!("Failed to read CA bundle {}: {}", path, e))?;
+    let certificates = Certificate::from_pem_bundle(&pem)
+        .map_err(|e| format!("Failed to parse CA bundle {}: {}", path, e))?;
+
+    if certificates.is_empty() {
+        return Err(format!("No certificates found in CA bundle {}", path));
+    }
+    Ok(certificates)
+}
+
+fn create_proxy(settings: &NetworkSettings) -> Result<Option<Proxy>, String> {
+    let Some(proxy_url) = settings.proxy_url.as_deref() else {
+        return Ok(None);
+    };
+
+    let mut proxy =
+        Proxy::all(proxy_url).map_err(|e| format!("Invalid proxy URL {}: {}", proxy_url, e))?;
+    if let Some(username) = settings.proxy_username.as_deref() {
+        proxy = proxy.basic_auth(username, settings.proxy_password.as_deref().unwrap_or(""));
+    }
+    if let Some(no_proxy) = settings.no_proxy.as_deref() {
+        proxy = proxy.no_proxy(NoProxy::from_string(no_proxy));
+    }
+
+    Ok(Some(proxy))
+}
+
+/// Apply the configured proxy and extra CA certificates to an HTTP client
+pub fn configure_http_client(
+    builder: ClientBuilder,
+    settings: &NetworkSettings,
+) -> Result<ClientBuilder, String> {
+    let builder = match create_proxy(settings)? {
+        Some(proxy) => builder.proxy(proxy),
+        None => builder,
+    };
+
+    match settings.ca_bundle_path.as_deref() {
+        Some(path) => Ok(builder.tls_certs_merge(load_ca_certificates(path)?)),
+        None => Ok(builder),
+    }
+}
+
+/// Chromium flags routing the scraper browser through the configured proxy. Chromium takes
+/// no credentials on the command line, so an authenticated proxy is rejected up front
+/// instead of failing on the first page load.
+pub fn browser_proxy_args(settings: &NetworkSettings) -> Result<Vec<String>, String> {
+    let Some(proxy_url) = settings.proxy_url.as_deref() else {
+        return Ok(Vec::new());
+    };
+    if settings.proxy_username.is_some() || settings.proxy_password.is_some() {
+        return Err(
+            "The scraper browser cannot sign in to a proxy; allow the marketplace through the proxy without authentication or add it to the no-proxy hosts"
+                .to_string(),
+        );
+    }
+
+    let bypass_list = settings.no_proxy.as_deref().map(|no_proxy| {
+        no_proxy
+            .split(',')
+            .map(str::trim)
+            .filter(|host| !host.is_empty())
+            .collect::<Vec<_>>()
+            .join(";")
+    });
+
+    Ok(std::iter::once(format!("--proxy-server={}", proxy_url))
+        .chain(bypass_list.map(|hosts| format!("--proxy-bypass-list={}", hosts)))
+        .collect())
+}
+
+/// Build number of an installer file name: `Some(None)` for `Mendix-11.0.0-Setup.exe`,
+/// `Some(Some(12345))` for `Mendix-10.24.0.12345-Setup.exe`, and `None` when the file is not
+/// an installer of `version` for `platform`
+fn installer_build_number(
+    file_name: &str,
+    version: &str,
+    platform: ArtifactPlatform,
+) -> Option<Option<u64>> {
+    let rest = file_name
+        .strip_prefix("Mendix-")?
+        .strip_suffix(platform.file_suffix())?
+        .strip_suffix('-')?
+        .strip_prefix(version)?;
+
+    if rest.is_empty() {
+        return Some(None);
+    }
+    rest.strip_prefix('.')
+        .filter(|build| !build.is_empty() && build.chars().all(|c| c.is_ascii_digit()))
+        .and_then(|build| build.parse().ok())
+        .map(Some)
+}
+
+/// `Mendix-11.0.0-Setup.exe`, or `Mendix-10.24.0.12345-Setup.exe` with any build number,
+/// with the file suffix of `platform`
+pub fn is_installer_for_version(
+    file_name: &str,
+    version: &str,
+    platform: ArtifactPlatform,
+) -> bool {
+    installer_build_number(file_name, version, platform).is_some()
+}
+
+/// Installer of a version for `platform` in a local folder repository, preferring the highest
+/// build number
+pub fn find_installer_in_repository(
+    repository: &Path,
+    version: &str,
+    platform: ArtifactPlatform,
+) -> Option<PathBuf> {
+    fs::read_dir(repository)
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .filter_map(|path| {
+            let build = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| installer_build_number(name, version, platform))?;
+            Some((build, path))
+        })
+        .max_by_key(|(build, _)| *build)
+        .map(|(_, path)| path)
+}
+
+fn validate_url(name: &str, url: Option<&str>) -> Result<(), String> {
+    match url.map(Url::parse) {
+        Some(Ok(parsed)) if matches!(parsed.scheme(), "http" | "https") => Ok(()),
+        Some(_) => Err(format!("Invalid {}: {}", name, url.unwrap_or_default())),
+        None => Ok(()),
+    }
+}
+
+fn validate_network_settings(settings: &NetworkSettings) -> Result<(), String> {
+    validate_url("marketplace URL", settings.marketplace_url.as_deref())?;
+    validate_url("artifacts URL", settings.artifacts_base_url.as_deref())?;
+    create_proxy(settings)?;
+
+    if let Some(path) = settings.ca_bundle_path.as_deref() {
+        load_ca_certificates(path)?;
+    }
+    if let Some(path) = settings.local_repository_path.as_deref() {
+        if !Path::new(path).is_dir() {
+            return Err(format!("Local repository folder not found: {}", path));
+        }
+    }
+
+    Ok(())
+}
+
+/// Network settings without the proxy password
+#[tauri::command]
+pub fn get_network_settings() -> Result<NetworkSettings, String> {
+    Ok(load_network_settings().without_password())
+}
+
+/// Save network settings; an empty proxy password keeps the stored one for the same user
+#[tauri::command]
+pub fn save_network_settings(settings: NetworkSettings) -> Result<NetworkSettings, String> {
+    let config = ScraperConfig::load().unwrap_or_default();
+    let settings = settings.normalized().with_stored_password(&config.network);
+    validate_network_settings(&settings)?;
+
+    config.with_network(settings.clone()).save()?;
+    Ok(settings.without_password())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_installer_for_version() {
+        assert!(is_installer_for_version(
+            "Mendix-11.0.0-Setup.exe",
+            "11.0.0",
+            ArtifactPlatform::Windows
+        ));
+        assert!(is_installer_for_version(
+            "Mendix-10.24.0.12345-Setup.exe",
+            "10.24.0",
+            ArtifactPlatform::Windows
+        ));
+        assert!(!is_installer_for_version(
+            "Mendix-10.24.01.12345-Setup.exe",
+            "10.24.0",
+            ArtifactPlatform::Windows
+        ));
+        assert!(!is_installer_for_version(
+            "Mendix-10.24.0.-Setup.exe",
+            "10.24.0",
+            ArtifactPlatform::Windows
+        ));
+        assert!(!is_installer_for_version(
+            "Mendix-10.24.0.12345.zip",
+            "10.24.0",
+            ArtifactPlatform::Windows
+        ));
+        assert!(is_installer_for_version(
+            "Mendix-11.0.0-macos-x64.dmg",
+            "11.0.0",
+            ArtifactPlatform::MacosX64
+        ));
+        assert!(!is_installer_for_version(
+            "Mendix-11.0.0-macos-x64.dmg",
+            "11.0.0",
+            ArtifactPlatform::Windows
+        ));
+    }
+
+    #[test]
+    fn test_find_installer_in_repository() {
+        let dir = tempfile::tempdir().unwrap();
+        for name in [
+            "Mendix-10.24.0.11111-Setup.exe",
+            "Mendix-10.24.0.9999-Setup.exe",
+            "Mendix-10.24.0.22222-Setup.exe",
+            "Mendix-10.24.0-Setup.exe",
+            "Mendix-10.24.1.33333-Setup.exe",
+            "Mendix-10.24.0.22222-macos-arm64.dmg",
+        ] {
+            fs::write(dir.path().join(name), b"").unwrap();
+        }
+
+        assert_eq!(
+            find_installer_in_repository(dir.path(), "10.24.0", ArtifactPlatform::Windows),
+            Some(dir.path().join("Mendix-10.24.0.22222-Setup.exe"))
+        );
+        assert_eq!(
+            find_installer_in_repository(dir.path(), "10.24.0", ArtifactPlatform::MacosArm64),
+            Some(dir.path().join("Mendix-10.24.0.22222-macos-arm64.dmg"))
+        );
+        assert_eq!(
+            find_installer_in_repository(dir.path(), "10.24.0", ArtifactPlatform::MacosX64),
+            None
+        );
+        assert_eq!(
+            find_installer_in_repository(dir.path(), "10.23.0", ArtifactPlatform::Windows),
+            None
+        );
+    }
+
+    #[test]
+    fn test_network_settings_defaults_and_validation() {
+        let settings = NetworkSettings {
+            artifacts_base_url: Some(" https://mirror.example.com/modelers/ ".to_string()),
+            no_proxy: Some("  ".to_string()),
+            ..NetworkSettings::default()
+        }
+        .normalized();
+
+        assert_eq!(
+            artifacts_base_url(&settings),
+            "https://mirror.example.com/modelers"
+        );
+        assert_eq!(marketplace_url(&settings), MENDIX_MARKETPLACE_URL);
+        assert_eq!(settings.no_proxy, None);
+        assert!(validate_network_settings(&settings).is_ok());
+
+        let invalid = NetworkSettings {
+            marketplace_url: Some("ftp://example.com".to_string()),
+            ..NetworkSettings::default()
+        };
+        assert!(validate_network_settings(&invalid).is_err());
+    }
+
+    #[test]
+    fn test_browser_proxy_args() {
+        let settings = NetworkSettings {
+            proxy_url: Some("http://proxy.corp:8080".to_string()),
+            no_proxy: Some("localhost, .corp".to_string()),
+            ..NetworkSettings::default()
+        };
+
+        assert_eq!(
+            browser_proxy_args(&settings).unwrap(),
+            vec![
+                "--proxy-server=http://proxy.corp:8080",
+                "--proxy-bypass-list=localhost;.corp"
+            ]
+        );
+        assert!(browser_proxy_args(&NetworkSettings::default())
+            .unwrap()
+            .is_empty());
+
+        let authenticated = NetworkSettings {
+            proxy_username: Some("builder".to_string()),
+            ..settings
+        };
+        assert!(browser_proxy_args(&authenticated).is_err());
+    }
+
+    #[test]
+    fn test_proxy_password_is_kept_but_not_returned() {
+        let stored = NetworkSettings {
+            proxy_url: Some("http://proxy.corp:8080".to_string()),
+            proxy_username: Some("builder".to_string()),
+            proxy_password: Some("secret".to_string()),
+            ..NetworkSettings::default()
+        };
+
+        let from_ui = stored.clone().without_password();
+        assert_eq!(from_ui.proxy_password, None);
+        assert_eq!(
+            from_ui.clone().with_stored_password(&stored).proxy_password,
+            Some("secret".to_string())
+        );
+
+        let other_user = NetworkSettings {
+            proxy_username: Some("deployer".to_string()),
+            ..from_ui
+        };
+        assert_eq!(
+            other_user.with_stored_password(&stored).proxy_password,
+            None
+        );
+
+        let new_password = NetworkSettings {
+            proxy_password: Some("rotated".to_string()),
+            ..stored.clone()
+        };
+        assert_eq!(
+            new_password.with_stored_password(&stored).proxy_password,
+            Some("rotated".to_string())
+        );
+    }
+}
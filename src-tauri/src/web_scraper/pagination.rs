@@ -0,0 +1,231 @@
+use chromiumoxide::page::Page;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+use crate::storage::{load_downloadable_versions_cache, merge_and_save_downloadable_versions};
+
+use super::browser::{click_next_page_button, handle_privacy_modal_if_present, BrowserSession};
+use super::config::{
+    parse_version_parts, DownloadableVersion, ScrapingConfig, PAGINATION_DELAY_MS,
+};
+use super::freshness::notify_new_versions;
+use super::network::{artifacts_base_url, load_network_settings, marketplace_url};
+use super::parsing::{extract_datagrid_content, parse_datagrid_html};
+use super::selectors::{load_selector_profile, SelectorProfile};
+
+/// Tauri event carrying `DownloadableVersionsPage` as each datagrid page is scraped
+pub const DOWNLOADABLE_VERSIONS_PAGE_EVENT: &str = "mendix-downloadable-versions-page";
+
+/// Upper bound on pages walked in one session, in case the last page is never detected
+const MAX_DATAGRID_PAGES: u32 = 100;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DownloadableVersionsPage {
+    pub page: u32,
+    pub versions: Vec<DownloadableVersion>,
+    pub reached_floor: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct DatagridScrapeSummary {
+    pub pages_scraped: u32,
+    pub versions_found: usize,
+    pub reached_floor: bool,
+    pub versions: Vec<DownloadableVersion>,
+}
+
+/// `"< 9.0"` or `"9.0"` -> `[9, 0]`
+pub fn parse_version_floor(floor: &str) -> Result<Vec<u32>, String> {
+    let version = floor.trim().trim_start_matches('<').trim();
+    let parts: Result<Vec<u32>, _> = version.split('.').map(str::parse).collect();
+
+    parts
+        .ok()
+        .filter(|parts| !parts.is_empty())
+        .ok_or_else(|| format!("Invalid version floor: {}", floor))
+}
+
+/// Drop versions older than the floor; the flag tells whether the whole page was below it.
+/// The datagrid is ordered by release date, so patches of older majors show up between
+/// newer releases and a single old row does not end the walk.
+pub fn apply_version_floor(
+    versions: Vec<DownloadableVersion>,
+    floor: Option<&[u32]>,
+) -> (Vec<DownloadableVersion>, bool) {
+    let Some(floor) = floor else {
+        return (versions, false);
+    };
+
+    let total = versions.len();
+    let kept: Vec<DownloadableVersion> = versions
+        .into_iter()
+        .filter(|version| parse_version_parts(&version.version).as_slice() >= floor)
+        .collect();
+    let reached_floor = total > 0 && kept.is_empty();
+
+    (kept, reached_floor)
+}
+
+/// Read the datagrid after a page change, waiting once more if it still shows the previous page
+async fn extract_next_datagrid_content(
+    page: &Page,
+    config: &ScrapingConfig,
+    profile: &SelectorProfile,
+    previous_html: &str,
+) -> Result<Option<String>, String> {
+    for _ in 0..2 {
+        tokio::time::sleep(Duration::from_millis(PAGINATION_DELAY_MS)).await;
+        let html_content = extract_datagrid_content(page, config, profile).await?;
+        if html_content != previous_html {
+            return Ok(Some(html_content));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Walk the marketplace datagrid in one browser session, emitting and caching every page,
+/// until the last page, `max_pages`, or a page reaching below `floor`
+pub(crate) async fn walk_datagrid_pages(
+    app_handle: &AppHandle,
+    floor: Option<&[u32]>,
+    max_pages: u32,
+) -> Result<DatagridScrapeSummary, String> {
+    let config = ScrapingConfig::default();
+    let network = load_network_settings();
+    let profile = load_selector_profile();
+
+    let session = BrowserSession::new().await?;
+
+    let result = async {
+        let page_instance = session.navigate(marketplace_url(&network)).await?;
+        handle_privacy_modal_if_present(&page_instance, &profile).await?;
+
+        let mut summary = DatagridScrapeSummary::default();
+        let mut html_content = extract_datagrid_content(&page_instance, &config, &profile).await?;
+
+        for page_number in 1..=max_pages {
+            let (versions, reached_floor) = apply_version_floor(
+                parse_datagrid_html(&html_content, &profile)?
+                    .into_iter()
+                    .map(|version| version.with_platform_artifacts(artifacts_base_url(&network)))
+                    .collect(),
+                floor,
+            );
+            println!(
+                "[Datagrid] Page {}: {} versions",
+                page_number,
+                versions.len()
+            );
+
+            summary.versions = merge_and_save_downloadable_versions(versions.clone())?;
+            summary.pages_scraped = page_number;
+            summary.versions_found += versions.len();
+            summary.reached_floor = reached_floor;
+
+            let _ = app_handle.emit(
+                DOWNLOADABLE_VERSIONS_PAGE_EVENT,
+                DownloadableVersionsPage {
+                    page: page_number,
+                    versions,
+                    reached_floor,
+                },
+            );
+
+            if reached_floor || page_number == max_pages {
+                break;
+            }
+            if !click_next_page_button(&page_instance, &profile).await? {
+                break;
+            }
+
+            match extract_next_datagrid_content(&page_instance, &config, &profile, &html_content)
+                .await?
+            {
+                Some(next_html) => html_content = next_html,
+                None => {
+                    println!("[Datagrid] Page content did not change, stopping");
+                    break;
+                }
+            }
+        }
+
+        Ok(summary)
+    }
+    .await;
+
+    let _ = session.cleanup().await;
+    result
+}
+
+/// Scrape every datagrid page, or down to `version_floor` such as `"< 9.0"`, and notify
+/// about new releases compared with the previous cache
+#[tauri::command]
+pub async fn scrape_all_downloadable_versions(
+    app_handle: AppHandle,
+    version_floor: Option<String>,
+    max_pages: Option<u32>,
+) -> Result<DatagridScrapeSummary, String> {
+    let floor = version_floor
+        .as_deref()
+        .map(parse_version_floor)
+        .transpose()?;
+    let max_pages = max_pages.unwrap_or(MAX_DATAGRID_PAGES).max(1);
+
+    let previous = load_downloadable_versions_cache().unwrap_or_default();
+    let summary = walk_datagrid_pages(&app_handle, floor.as_deref(), max_pages).await?;
+    notify_new_versions(&app_handle, &previous, &summary.versions);
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::web_scraper::config::VersionFlags;
+
+    fn downloadable(version: &str) -> DownloadableVersion {
+        DownloadableVersion::new(
+            version.to_string(),
+            String::new(),
+            None,
+            None,
+            None,
+            VersionFlags::default(),
+        )
+    }
+
+    #[test]
+    fn test_parse_version_floor() {
+        assert_eq!(parse_version_floor("< 9.0").unwrap(), vec![9, 0]);
+        assert_eq!(parse_version_floor("10.24").unwrap(), vec![10, 24]);
+        assert!(parse_version_floor("<").is_err());
+        assert!(parse_version_floor("nine").is_err());
+    }
+
+    #[test]
+    fn test_apply_version_floor() {
+        let page = vec![
+            downloadable("10.24.1"),
+            downloadable("9.24.30"),
+            downloadable("10.23.0"),
+        ];
+
+        let (kept, reached_floor) = apply_version_floor(page.clone(), Some(&[10, 0]));
+        assert!(!reached_floor);
+        assert_eq!(
+            kept.iter().map(|v| v.version.as_str()).collect::<Vec<_>>(),
+            vec!["10.24.1", "10.23.0"]
+        );
+
+        let old_page = vec![downloadable("9.24.29"), downloadable("9.23.0")];
+        let (kept, reached_floor) = apply_version_floor(old_page, Some(&[10, 0]));
+        assert!(reached_floor);
+        assert!(kept.is_empty());
+
+        let (kept, reached_floor) = apply_version_floor(page, None);
+        assert!(!reached_floor);
+        assert_eq!(kept.len(), 3);
+    }
+}
@@ -3,24 +3,44 @@ use regex::Regex;
 use scraper::{Html, Selector};
 
 use super::browser::{extract_element_html, wait_for_element_with_timeout};
-use super::config::{DownloadableVersion, ScrapingConfig, VersionFlags};
-
-/// Extract build number from text like "Build 12345"
-pub fn extract_build_number_from_text(text: &str) -> Option<String> {
-    Regex::new(r"Build\s+(\d{5})")
+use super::config::{
+    construct_release_notes_url, DownloadableVersion, ScrapingConfig, VersionFlags,
+};
+use super::selectors::SelectorProfile;
+
+/// Extract build number from text like "Build 12345" using the profile's pattern
+pub fn extract_build_number_from_text(text: &str, pattern: &str) -> Option<String> {
+    Regex::new(pattern)
         .ok()?
         .captures(text)?
         .get(1)
         .map(|m| m.as_str().to_string())
 }
 
-/// Parse a single row from the datagrid
-fn parse_datagrid_row(row: &scraper::ElementRef) -> Option<DownloadableVersion> {
-    let version_selector = Selector::parse("div[role=gridcell] > div > div > a").ok()?;
-    let span_selector = Selector::parse("div[role=gridcell] > div > div > span").ok()?;
+/// Extract build number from the HTML of a version page
+pub fn extract_build_number_from_html(
+    html_content: &str,
+    profile: &SelectorProfile,
+) -> Option<String> {
+    let document = Html::parse_document(html_content);
+    let selector = Selector::parse(&profile.build_number_text).ok()?;
+
+    document.select(&selector).find_map(|element| {
+        extract_build_number_from_text(
+            &element.text().collect::<String>(),
+            &profile.build_number_pattern,
+        )
+    })
+}
 
+/// Parse a single row from the datagrid
+fn parse_datagrid_row(
+    row: &scraper::ElementRef,
+    version_selector: &Selector,
+    badge_selector: &Selector,
+) -> Option<DownloadableVersion> {
     let version_text = row
-        .select(&version_selector)
+        .select(version_selector)
         .next()?
         .text()
         .collect::<String>()
@@ -28,7 +48,7 @@ fn parse_datagrid_row(row: &scraper::ElementRef) -> Option<DownloadableVersion>
         .to_string();
 
     let flags = row
-        .select(&span_selector)
+        .select(badge_selector)
         .map(|span| span.text().collect::<String>().trim().to_uppercase())
         .fold(VersionFlags::default(), |mut flags, text| {
             match text.as_str() {
@@ -41,25 +61,34 @@ fn parse_datagrid_row(row: &scraper::ElementRef) -> Option<DownloadableVersion>
             flags
         });
 
+    let release_notes_url = construct_release_notes_url(&version_text);
+
     Some(DownloadableVersion::new(
         version_text,
         String::new(),
         None,
-        None,
+        release_notes_url,
         None,
         flags,
     ))
 }
 
 /// Parse datagrid HTML content into downloadable versions
-pub fn parse_datagrid_html(html_content: &str) -> Result<Vec<DownloadableVersion>, String> {
+pub fn parse_datagrid_html(
+    html_content: &str,
+    profile: &SelectorProfile,
+) -> Result<Vec<DownloadableVersion>, String> {
     let document = Html::parse_document(html_content);
-    let row_selector = Selector::parse("div.tr[role=row]")
+    let row_selector = Selector::parse(&profile.datagrid_row)
         .map_err(|e| format!("Failed to parse row selector: {}", e))?;
+    let version_selector = Selector::parse(&profile.version_link)
+        .map_err(|e| format!("Failed to parse version selector: {}", e))?;
+    let badge_selector = Selector::parse(&profile.version_badge)
+        .map_err(|e| format!("Failed to parse badge selector: {}", e))?;
 
     let versions = document
         .select(&row_selector)
-        .filter_map(|row| parse_datagrid_row(&row))
+        .filter_map(|row| parse_datagrid_row(&row, &version_selector, &badge_selector))
         .collect();
 
     Ok(versions)
@@ -69,10 +98,14 @@ pub fn parse_datagrid_html(html_content: &str) -> Result<Vec<DownloadableVersion
 pub async fn extract_datagrid_content(
     page: &Page,
     config: &ScrapingConfig,
+    profile: &SelectorProfile,
 ) -> Result<String, String> {
-    let selector = "div.widget-datagrid-content";
-    let elements =
-        wait_for_element_with_timeout(page, selector, config.wait_for_element_seconds).await?;
+    let elements = wait_for_element_with_timeout(
+        page,
+        &profile.datagrid_content,
+        config.wait_for_element_seconds,
+    )
+    .await?;
 
     if let Some(element) = elements.into_iter().next() {
         extract_element_html(&element).await
@@ -85,6 +118,7 @@ pub async fn extract_datagrid_content(
 pub async fn extract_build_number_from_marketplace(
     page: &Page,
     version: &str,
+    profile: &SelectorProfile,
 ) -> Result<String, String> {
     use std::time::Duration;
 
@@ -93,23 +127,121 @@ pub async fn extract_build_number_from_marketplace(
     ))
     .await;
 
-    let selector = "span.mx-text.pds-heading--sm.pds-mb-0";
-
-    let elements = page
-        .find_elements(selector)
+    let html_content = page
+        .content()
         .await
-        .map_err(|e| format!("Failed to find elements: {}", e))?;
-
-    for element in elements {
-        if let Ok(Some(text)) = element.inner_text().await {
-            if text.contains("Build") {
-                if let Some(build_number) = extract_build_number_from_text(&text) {
-                    println!("✅ Build number extracted: {}", build_number);
-                    return Ok(build_number);
-                }
-            }
+        .map_err(|e| format!("Failed to read page content: {}", e))?;
+
+    let build_number = extract_build_number_from_html(&html_content, profile)
+        .ok_or_else(|| format!("Build number not found for version {}", version))?;
+    println!("✅ Build number extracted: {}", build_number);
+    Ok(build_number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATAGRID_PAGE: &str = include_str!("../../tests/fixtures/marketplace_datagrid_page.html");
+    const DATAGRID_REDESIGN: &str =
+        include_str!("../../tests/fixtures/marketplace_datagrid_redesign.html");
+    const VERSION_PAGE: &str = include_str!("../../tests/fixtures/marketplace_version_page.html");
+
+    fn flags_of(version: &DownloadableVersion) -> (bool, bool, bool, bool) {
+        (
+            version.is_lts,
+            version.is_mts,
+            version.is_latest,
+            version.is_beta,
+        )
+    }
+
+    #[test]
+    fn test_parse_datagrid_fixture() {
+        let versions = parse_datagrid_html(DATAGRID_PAGE, &SelectorProfile::default()).unwrap();
+
+        let expected = [
+            ("11.1.0", (false, false, true, false)),
+            ("11.0.0", (false, false, false, true)),
+            ("10.24.3", (true, false, false, false)),
+            ("10.18.9", (false, true, false, false)),
+            ("10.23.0", (false, false, false, false)),
+        ];
+        assert_eq!(versions.len(), expected.len());
+        for (version, (expected_version, expected_flags)) in versions.iter().zip(expected) {
+            assert_eq!(version.version, expected_version);
+            assert_eq!(flags_of(version), expected_flags, "{}", expected_version);
+        }
+    }
+
+    #[test]
+    fn test_parse_datagrid_with_overridden_profile() {
+        let profile = SelectorProfile {
+            datagrid_row: "li.release-row".to_string(),
+            version_link: "a.release-row__version".to_string(),
+            version_badge: "span.release-row__badge".to_string(),
+            ..SelectorProfile::default()
+        };
+
+        let versions = parse_datagrid_html(DATAGRID_REDESIGN, &profile).unwrap();
+        assert_eq!(versions.len(), 2);
+        assert_eq!(versions[0].version, "11.2.0");
+        assert!(versions[0].is_latest);
+        assert!(versions[1].is_lts);
+
+        assert!(
+            parse_datagrid_html(DATAGRID_REDESIGN, &SelectorProfile::default())
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_extract_build_number_from_version_page() {
+        let profile = SelectorProfile::default();
+
+        assert_eq!(
+            extract_build_number_from_html(VERSION_PAGE, &profile).as_deref(),
+            Some("79370")
+        );
+        assert_eq!(
+            extract_build_number_from_html(DATAGRID_PAGE, &profile),
+            None
+        );
+
+        let cases = [
+            ("Build 79370", Some("79370")),
+            ("Build\t12345 (LTS)", Some("12345")),
+            ("Build 1234", None),
+            ("Released on Jun 12", None),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(
+                extract_build_number_from_text(text, &profile.build_number_pattern).as_deref(),
+                expected,
+                "{}",
+                text
+            );
         }
     }
 
-    Err(format!("Build number not found for version {}", version))
+    #[test]
+    fn test_parse_datagrid_html_fills_release_notes_url() {
+        let html = r##"
+            <div class="widget-datagrid-content">
+              <div class="tr" role="row">
+                <div role="gridcell"><div><div><a href="#">10.24.3</a></div></div></div>
+                <div role="gridcell"><div><div><span>LTS</span></div></div></div>
+              </div>
+            </div>"##;
+
+        let versions = parse_datagrid_html(html, &SelectorProfile::default()).unwrap();
+
+        assert_eq!(versions.len(), 1);
+        assert!(versions[0].is_lts);
+        assert_eq!(
+            versions[0].release_notes_url.as_deref(),
+            Some("https://docs.mendix.com/releasenotes/studio-pro/10.24/#10243")
+        );
+    }
 }
@@ -0,0 +1,411 @@
+use regex::Regex;
+use scraper::{ElementRef, Html, Selector};
+use semver::Version;
+use serde::{Deserialize, Serialize};
+
+use super::config::{construct_release_notes_page_url, split_major_minor};
+use super::network::{configure_http_client, load_network_settings};
+
+/// Upper bound on release notes pages fetched for one changelog
+const MAX_CHANGELOG_PAGES: usize = 40;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReleaseNoteCategory {
+    NewFeatures,
+    BreakingChanges,
+    Fixes,
+    KnownIssues,
+    Deprecations,
+}
+
+/// Order categories appear in within a version and in a changelog
+const CATEGORY_ORDER: [ReleaseNoteCategory; 5] = [
+    ReleaseNoteCategory::BreakingChanges,
+    ReleaseNoteCategory::NewFeatures,
+    ReleaseNoteCategory::Fixes,
+    ReleaseNoteCategory::KnownIssues,
+    ReleaseNoteCategory::Deprecations,
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReleaseNoteSection {
+    pub category: ReleaseNoteCategory,
+    pub items: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct VersionReleaseNotes {
+    pub version: String,
+    pub release_date: Option<String>,
+    pub sections: Vec<ReleaseNoteSection>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ChangelogEntry {
+    pub version: String,
+    pub text: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ChangelogSection {
+    pub category: ReleaseNoteCategory,
+    pub entries: Vec<ChangelogEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReleaseNotesChangelog {
+    pub from_version: String,
+    pub to_version: String,
+    pub versions: Vec<VersionReleaseNotes>,
+    pub sections: Vec<ChangelogSection>,
+}
+
+/// Map a section heading to its category; "Improvements" count as new features
+fn category_from_heading(heading: &str) -> Option<ReleaseNoteCategory> {
+    let heading = heading.to_lowercase();
+
+    if heading.starts_with("new feature") || heading.starts_with("improvement") {
+        Some(ReleaseNoteCategory::NewFeatures)
+    } else if heading.starts_with("breaking change") {
+        Some(ReleaseNoteCategory::BreakingChanges)
+    } else if heading.starts_with("fix") {
+        Some(ReleaseNoteCategory::Fixes)
+    } else if heading.starts_with("known issue") {
+        Some(ReleaseNoteCategory::KnownIssues)
+    } else if heading.starts_with("deprecation") {
+        Some(ReleaseNoteCategory::Deprecations)
+    } else {
+        None
+    }
+}
+
+fn normalize_text(element: &ElementRef) -> String {
+    element
+        .text()
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn is_nested_in_list_item(element: &ElementRef) -> bool {
+    element
+        .ancestors()
+        .filter_map(ElementRef::wrap)
+        .any(|ancestor| ancestor.value().name() == "li")
+}
+
+fn add_item(notes: &mut VersionReleaseNotes, category: ReleaseNoteCategory, item: String) {
+    match notes
+        .sections
+        .iter_mut()
+        .find(|section| section.category == category)
+    {
+        Some(section) => section.items.push(item),
+        None => notes.sections.push(ReleaseNoteSection {
+            category,
+            items: vec![item],
+        }),
+    }
+}
+
+fn sort_sections(notes: &mut VersionReleaseNotes) {
+    notes.sections.sort_by_key(|section| {
+        CATEGORY_ORDER
+            .iter()
+            .position(|category| *category == section.category)
+    });
+}
+
+/// Parse a Studio Pro release notes page; each `h2` starts a version and each `h3`
+/// a section, with list items collected as entries
+pub fn parse_release_notes_html(html_content: &str) -> Result<Vec<VersionReleaseNotes>, String> {
+    let document = Html::parse_document(html_content);
+    let content_selector = Selector::parse(".td-content, main")
+        .map_err(|e| format!("Failed to parse content selector: {}", e))?;
+    let node_selector = Selector::parse("h2, h3, p, li")
+        .map_err(|e| format!("Failed to parse release notes selector: {}", e))?;
+    let version_regex = Regex::new(r"^(\d+\.\d+\.\d+)")
+        .map_err(|e| format!("Failed to compile version regex: {}", e))?;
+    let date_regex = Regex::new(r"(?i)release date:\s*(.+)")
+        .map_err(|e| format!("Failed to compile date regex: {}", e))?;
+
+    let root = document
+        .select(&content_selector)
+        .next()
+        .unwrap_or_else(|| document.root_element());
+
+    let mut releases: Vec<VersionReleaseNotes> = Vec::new();
+    let mut category: Option<ReleaseNoteCategory> = None;
+
+    for element in root.select(&node_selector) {
+        let text = normalize_text(&element);
+
+        match element.value().name() {
+            "h2" => {
+                category = None;
+                if let Some(captures) = version_regex.captures(&text) {
+                    releases.push(VersionReleaseNotes {
+                        version: captures[1].to_string(),
+                        release_date: None,
+                        sections: Vec::new(),
+                    });
+                }
+            }
+            "h3" => category = category_from_heading(&text),
+            "p" if !is_nested_in_list_item(&element) => {
+                if let (Some(current), Some(captures)) =
+                    (releases.last_mut(), date_regex.captures(&text))
+                {
+                    if current.release_date.is_none() {
+                        current.release_date = Some(captures[1].trim().to_string());
+                    }
+                }
+            }
+            "li" if !is_nested_in_list_item(&element) && !text.is_empty() => {
+                if let (Some(current), Some(category)) = (releases.last_mut(), category) {
+                    add_item(current, category, text);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    releases.iter_mut().for_each(sort_sections);
+    Ok(releases)
+}
+
+fn parse_version(version: &str) -> Result<Version, String> {
+    Version::parse(version.trim()).map_err(|e| format!("Invalid version {}: {}", version, e))
+}
+
+/// Combine the notes of every version after `from_version` up to and including `to_version`
+pub fn build_changelog(
+    notes: &[VersionReleaseNotes],
+    from_version: &str,
+    to_version: &str,
+) -> Result<ReleaseNotesChangelog, String> {
+    let from = parse_version(from_version)?;
+    let to = parse_version(to_version)?;
+
+    let mut versions: Vec<(Version, VersionReleaseNotes)> = notes
+        .iter()
+        .filter_map(|release| {
+            Version::parse(&release.version)
+                .ok()
+                .map(|version| (version, release.clone()))
+        })
+        .filter(|(version, _)| *version > from && *version <= to)
+        .collect();
+    versions.sort_by(|(a, _), (b, _)| a.cmp(b));
+    versions.dedup_by(|(a, _), (b, _)| a == b);
+
+    let versions: Vec<VersionReleaseNotes> =
+        versions.into_iter().map(|(_, release)| release).collect();
+
+    let sections = CATEGORY_ORDER
+        .iter()
+        .map(|category| ChangelogSection {
+            category: *category,
+            entries: versions
+                .iter()
+                .flat_map(|release| {
+                    release
+                        .sections
+                        .iter()
+                        .filter(|section| section.category == *category)
+                        .flat_map(|section| section.items.iter())
+                        .map(|text| ChangelogEntry {
+                            version: release.version.clone(),
+                            text: text.clone(),
+                        })
+                })
+                .collect(),
+        })
+        .filter(|section| !section.entries.is_empty())
+        .collect();
+
+    Ok(ReleaseNotesChangelog {
+        from_version: from_version.to_string(),
+        to_version: to_version.to_string(),
+        versions,
+        sections,
+    })
+}
+
+/// Fetch one release notes page; `None` when the docs have no page for that minor version
+async fn fetch_release_notes_page(
+    client: &reqwest::Client,
+    major: u64,
+    minor: u64,
+) -> Result<Option<Vec<VersionReleaseNotes>>, String> {
+    let url = construct_release_notes_page_url(major, minor);
+    let response = client
+        .get(&url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch release notes: {}", e))?;
+
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(None);
+    }
+    if !response.status().is_success() {
+        return Err(format!(
+            "Release notes request failed with status: {}",
+            response.status()
+        ));
+    }
+
+    let html_content = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read release notes: {}", e))?;
+    parse_release_notes_html(&html_content).map(Some)
+}
+
+fn create_docs_client() -> Result<reqwest::Client, String> {
+    configure_http_client(reqwest::Client::builder(), &load_network_settings())?
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))
+}
+
+#[tauri::command]
+pub async fn get_release_notes(version: String) -> Result<VersionReleaseNotes, String> {
+    let (major, minor) =
+        split_major_minor(&version).ok_or_else(|| format!("Invalid version: {}", version))?;
+    let client = create_docs_client()?;
+
+    fetch_release_notes_page(&client, major, minor)
+        .await?
+        .and_then(|releases| {
+            releases
+                .into_iter()
+                .find(|release| release.version == version.trim())
+        })
+        .ok_or_else(|| format!("No release notes found for {}", version))
+}
+
+/// Changelog between an app's current version and a target version. Pages are walked
+/// minor by minor; a missing page moves on to the next major version.
+#[tauri::command]
+pub async fn get_release_notes_changelog(
+    from_version: String,
+    to_version: String,
+) -> Result<ReleaseNotesChangelog, String> {
+    let from = parse_version(&from_version)?;
+    let to = parse_version(&to_version)?;
+    if to <= from {
+        return Err(format!(
+            "Target version {} is not newer than {}",
+            to_version, from_version
+        ));
+    }
+
+    let client = create_docs_client()?;
+    let mut notes = Vec::new();
+    let (mut major, mut minor) = (from.major, from.minor);
+
+    for _ in 0..MAX_CHANGELOG_PAGES {
+        if (major, minor) > (to.major, to.minor) {
+            break;
+        }
+
+        match fetch_release_notes_page(&client, major, minor).await? {
+            Some(releases) => {
+                notes.extend(releases);
+                minor += 1;
+            }
+            None => {
+                major += 1;
+                minor = 0;
+            }
+        }
+    }
+
+    build_changelog(&notes, &from_version, &to_version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RELEASE_NOTES_10_4: &str = include_str!("../../tests/fixtures/release_notes_10_4.html");
+    const RELEASE_NOTES_10_5: &str = include_str!("../../tests/fixtures/release_notes_10_5.html");
+
+    fn section_items(release: &VersionReleaseNotes, category: ReleaseNoteCategory) -> Vec<&str> {
+        release
+            .sections
+            .iter()
+            .find(|section| section.category == category)
+            .map(|section| section.items.iter().map(String::as_str).collect())
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn test_parse_release_notes_html() {
+        let releases = parse_release_notes_html(RELEASE_NOTES_10_4).unwrap();
+
+        let versions: Vec<&str> = releases.iter().map(|r| r.version.as_str()).collect();
+        assert_eq!(versions, vec!["10.4.1", "10.4.0"]);
+
+        let patch = &releases[0];
+        assert_eq!(patch.release_date.as_deref(), Some("October 19, 2023"));
+        assert_eq!(
+            section_items(patch, ReleaseNoteCategory::Fixes),
+            vec![
+                "We fixed an issue where the Find Advanced dialog box showed duplicate results. (Ticket 198765)",
+                "We fixed a crash when importing a module package.",
+            ]
+        );
+        assert_eq!(
+            section_items(patch, ReleaseNoteCategory::KnownIssues),
+            vec!["Renaming an entity can break XPath constraints in microflows. Fixed in 10.5.0."]
+        );
+
+        let minor = &releases[1];
+        let categories: Vec<_> = minor.sections.iter().map(|s| s.category).collect();
+        assert_eq!(
+            categories,
+            vec![
+                ReleaseNoteCategory::BreakingChanges,
+                ReleaseNoteCategory::NewFeatures,
+                ReleaseNoteCategory::Fixes,
+                ReleaseNoteCategory::Deprecations,
+            ]
+        );
+        assert_eq!(
+            section_items(minor, ReleaseNoteCategory::NewFeatures).len(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_build_changelog_between_versions() {
+        let notes: Vec<VersionReleaseNotes> = [RELEASE_NOTES_10_4, RELEASE_NOTES_10_5]
+            .iter()
+            .flat_map(|html| parse_release_notes_html(html).unwrap())
+            .collect();
+
+        let changelog = build_changelog(&notes, "10.4.0", "10.5.0").unwrap();
+
+        let versions: Vec<&str> = changelog
+            .versions
+            .iter()
+            .map(|r| r.version.as_str())
+            .collect();
+        assert_eq!(versions, vec!["10.4.1", "10.5.0"]);
+
+        let fixes = changelog
+            .sections
+            .iter()
+            .find(|section| section.category == ReleaseNoteCategory::Fixes)
+            .unwrap();
+        let fix_versions: Vec<&str> = fixes.entries.iter().map(|e| e.version.as_str()).collect();
+        assert_eq!(fix_versions, vec!["10.4.1", "10.4.1", "10.5.0"]);
+        assert!(changelog
+            .sections
+            .iter()
+            .all(|section| section.category != ReleaseNoteCategory::BreakingChanges));
+    }
+}
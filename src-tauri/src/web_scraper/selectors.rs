@@ -0,0 +1,217 @@
+use regex::Regex;
+use scraper::Selector;
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+use crate::config::construct_config_file_path;
+
+/// File in the config directory that overrides the built-in selectors
+const SELECTOR_PROFILE_FILE: &str = "marketplace_selectors.json";
+
+/// Version of the built-in profile; bump it whenever the defaults follow a markup change
+/// so overrides written for older markup are ignored
+pub const SELECTOR_PROFILE_VERSION: u32 = 1;
+
+/// CSS selectors and patterns the scraper relies on; apart from `version`, missing fields in
+/// an override file fall back to the built-in values
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SelectorProfile {
+    pub version: u32,
+    pub datagrid_content: String,
+    pub datagrid_row: String,
+    pub version_link: String,
+    pub version_badge: String,
+    pub paging_status: String,
+    pub next_page_buttons: Vec<String>,
+    pub build_number_text: String,
+    /// Regex whose first group is the build number
+    pub build_number_pattern: String,
+    pub privacy_modals: Vec<String>,
+    pub privacy_reject_buttons: Vec<String>,
+}
+
+/// `version` of an override file, which has no default so unversioned files are rejected
+#[derive(Deserialize)]
+struct SelectorProfileVersion {
+    version: u32,
+}
+
+fn to_strings(values: &[&str]) -> Vec<String> {
+    values.iter().map(|value| value.to_string()).collect()
+}
+
+impl Default for SelectorProfile {
+    fn default() -> Self {
+        Self {
+            version: SELECTOR_PROFILE_VERSION,
+            datagrid_content: "div.widget-datagrid-content".to_string(),
+            datagrid_row: "div.tr[role=row]".to_string(),
+            version_link: "div[role=gridcell] > div > div > a".to_string(),
+            version_badge: "div[role=gridcell] > div > div > span".to_string(),
+            paging_status: "div.paging-status".to_string(),
+            next_page_buttons: to_strings(&[
+                "button[aria-label='Go to next page']",
+                ".pagination-button[aria-label='Go to next page']",
+                "button.pagination-button:nth-child(3)",
+            ]),
+            build_number_text: "span.mx-text.pds-heading--sm.pds-mb-0".to_string(),
+            build_number_pattern: r"Build\s+(\d{5})".to_string(),
+            privacy_modals: to_strings(&[
+                "[data-testid='uc-default-wall']",
+                ".cookie-banner",
+                "#cookie-banner",
+                "[class*='cookie']",
+                "[class*='privacy']",
+            ]),
+            privacy_reject_buttons: to_strings(&[
+                "[data-testid='uc-deny-all-button']",
+                "[data-testid='uc-reject-all-button']",
+                "button[class*='reject']",
+                "button[class*='deny']",
+                "button:contains('Reject')",
+                "button:contains('Deny')",
+            ]),
+        }
+    }
+}
+
+impl SelectorProfile {
+    /// Check that every selector and the build number pattern parse. Privacy button
+    /// selectors are only used through the browser, which accepts a wider syntax.
+    pub fn validate(&self) -> Result<(), String> {
+        let selectors = [
+            &self.datagrid_content,
+            &self.datagrid_row,
+            &self.version_link,
+            &self.version_badge,
+            &self.paging_status,
+            &self.build_number_text,
+        ]
+        .into_iter()
+        .chain(&self.next_page_buttons);
+
+        for selector in selectors {
+            Selector::parse(selector)
+                .map_err(|e| format!("Invalid selector '{}': {}", selector, e))?;
+        }
+
+        let build_number_pattern = Regex::new(&self.build_number_pattern)
+            .map_err(|e| format!("Invalid build number pattern: {}", e))?;
+        if build_number_pattern.captures_len() < 2 {
+            return Err(format!(
+                "Build number pattern '{}' has no capture group for the build number",
+                self.build_number_pattern
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Parse an override file; profiles without a version or written for an older one are rejected
+pub fn parse_selector_profile(content: &str) -> Result<SelectorProfile, String> {
+    let SelectorProfileVersion { version } = serde_json::from_str(content)
+        .map_err(|e| format!("Failed to parse selector profile: {}", e))?;
+    if version < SELECTOR_PROFILE_VERSION {
+        return Err(format!(
+            "Selector profile version {} is older than the built-in version {}",
+            version, SELECTOR_PROFILE_VERSION
+        ));
+    }
+
+    let profile: SelectorProfile = serde_json::from_str(content)
+        .map_err(|e| format!("Failed to parse selector profile: {}", e))?;
+
+    profile.validate()?;
+    Ok(profile)
+}
+
+/// Selectors from the override file when present and valid, the built-in ones otherwise
+pub fn load_selector_profile() -> SelectorProfile {
+    let override_content = construct_config_file_path(SELECTOR_PROFILE_FILE)
+        .ok()
+        .filter(|path| path.exists())
+        .and_then(|path| fs::read_to_string(path).ok());
+
+    match override_content.map(|content| parse_selector_profile(&content)) {
+        Some(Ok(profile)) => profile,
+        Some(Err(e)) => {
+            println!("[Selectors] Using built-in selectors: {}", e);
+            SelectorProfile::default()
+        }
+        None => SelectorProfile::default(),
+    }
+}
+
+#[tauri::command]
+pub fn get_selector_profile() -> Result<SelectorProfile, String> {
+    Ok(load_selector_profile())
+}
+
+/// Save an override profile; `None` removes it and returns to the built-in selectors
+#[tauri::command]
+pub fn save_selector_profile(profile: Option<SelectorProfile>) -> Result<SelectorProfile, String> {
+    let config_path = construct_config_file_path(SELECTOR_PROFILE_FILE)?;
+
+    match profile {
+        Some(profile) => {
+            let content = serde_json::to_string_pretty(&profile)
+                .map_err(|e| format!("Failed to serialize selector profile: {}", e))?;
+            parse_selector_profile(&content)?;
+
+            if let Some(parent) = config_path.parent() {
+                fs::create_dir_all(parent)
+                    .map_err(|e| format!("Failed to create config directory: {}", e))?;
+            }
+            fs::write(&config_path, content)
+                .map_err(|e| format!("Failed to write selector profile: {}", e))?;
+        }
+        None if config_path.exists() => {
+            fs::remove_file(&config_path)
+                .map_err(|e| format!("Failed to remove selector profile: {}", e))?;
+        }
+        None => {}
+    }
+
+    Ok(load_selector_profile())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_profile_is_valid() {
+        SelectorProfile::default().validate().unwrap();
+    }
+
+    #[test]
+    fn test_parse_selector_profile_merges_with_defaults() {
+        let profile = parse_selector_profile(
+            r#"{ "version": 1, "datagrid_row": "div.release-row", "next_page_buttons": ["button.next"] }"#,
+        )
+        .unwrap();
+
+        assert_eq!(profile.datagrid_row, "div.release-row");
+        assert_eq!(profile.next_page_buttons, vec!["button.next"]);
+        assert_eq!(
+            profile.datagrid_content,
+            SelectorProfile::default().datagrid_content
+        );
+    }
+
+    #[test]
+    fn test_parse_selector_profile_rejects_stale_or_invalid() {
+        assert!(parse_selector_profile(r#"{ "version": 0 }"#).is_err());
+        assert!(parse_selector_profile(r#"{ "datagrid_row": "div.release-row" }"#).is_err());
+        assert!(parse_selector_profile(
+            r#"{ "version": 1, "build_number_pattern": "Build \\d+" }"#
+        )
+        .is_err());
+        assert!(parse_selector_profile(r#"{ "version": 1, "datagrid_row": "div[" }"#).is_err());
+        assert!(
+            parse_selector_profile(r#"{ "version": 1, "build_number_pattern": "Build (" }"#)
+                .is_err()
+        );
+    }
+}
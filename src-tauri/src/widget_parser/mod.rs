@@ -708,7 +708,7 @@ fn state_to_widget_definition(state: ParseState) -> WidgetDefinition {
     }
 }
 
-fn parse_xml_content(xml_content: &str) -> Result<WidgetDefinition, ParseError> {
+pub fn parse_xml_content(xml_content: &str) -> Result<WidgetDefinition, ParseError> {
     parse_xml_events(xml_content, process_xml_event).map(state_to_widget_definition)
 }
 
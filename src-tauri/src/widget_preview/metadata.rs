@@ -53,7 +53,12 @@ pub fn find_widget_id_in_directory(src_dir: &Path) -> Option<String> {
 /// Extract widget id attribute from XML file using quick_xml
 pub fn extract_widget_id_from_xml(xml_path: &Path) -> Option<String> {
     let xml_content = std::fs::read_to_string(xml_path).ok()?;
-    let mut reader = Reader::from_str(&xml_content);
+    extract_widget_id_from_xml_content(&xml_content)
+}
+
+/// Extract widget id attribute from widget XML content
+pub fn extract_widget_id_from_xml_content(xml_content: &str) -> Option<String> {
+    let mut reader = Reader::from_str(xml_content);
     reader.config_mut().trim_text(true);
 
     loop {